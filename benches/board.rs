@@ -0,0 +1,130 @@
+#[macro_use]
+extern crate bencher;
+extern crate joseki;
+
+use bencher::Bencher;
+
+use joseki::{Board, Stone};
+
+/// Final position of game 1 of the AlphaGo vs. Lee Sedol match.
+const MIDGAME: &str = "\
+    .........O......... \
+    ........O#O.####OO. \
+    ..O..#..O#.##OOOO#. \
+    ...O..O.O#.#.#OO.O. \
+    ........O#O#.#O.O#. \
+    ..O...O##OO##.O#OO# \
+    ......OO.O#O..O#.#. \
+    OO...O##OO#OO.O##O. \
+    #OO..O#.#.##O..#.#. \
+    ##OO.O####..O..#O.. \
+    ....##....#O..#OO.. \
+    ####.....OO#O.####. \
+    .OO#....#..#OOO#... \
+    O..O#......#O.#.##. \
+    .OOO.......#O.##OOO \
+    OO#O#O#....#O.#OO.. \
+    O#.#.#O...##O.##O.. \
+    .O##.#...O#OO.#.#O. \
+    ..........#....#.O.";
+
+/// Minimal xorshift generator so that playouts are reproducible between runs.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as usize
+    }
+}
+
+/// Returns true if every on-board neighbour of `(x, y)` is a `stone`.
+fn is_eyelike(board: &Board, stone: Stone, x: usize, y: usize) -> bool {
+    let size = board.size;
+
+    (x == 0 || board[(x - 1, y)] == stone)
+        && (y == 0 || board[(x, y - 1)] == stone)
+        && (x + 1 == size || board[(x + 1, y)] == stone)
+        && (y + 1 == size || board[(x, y + 1)] == stone)
+}
+
+/// Plays uniformly random moves (never filling single-point eyes) until both players pass.
+fn playout(size: usize, rng: &mut XorShift) -> Board {
+    let mut board = Board::with_size(size);
+    let mut stone = Stone::Black;
+    let mut passes = 0;
+
+    for _ in 0..(size * size * 3) {
+        let mut empty: Vec<_> = (0..size * size)
+            .map(|i| (i % size, i / size))
+            .filter(|&p| board[p] == Stone::Empty)
+            .collect();
+
+        let mut played = false;
+
+        while !empty.is_empty() {
+            let (x, y) = empty.swap_remove(rng.next() % empty.len());
+            if !is_eyelike(&board, stone, x, y) && board.make_move(stone, (x, y)) {
+                played = true;
+                break;
+            }
+        }
+
+        passes = if played { 0 } else { passes + 1 };
+        if passes == 2 {
+            break;
+        }
+
+        stone = if stone == Stone::Black { Stone::White } else { Stone::Black };
+    }
+
+    board
+}
+
+fn make_move_empty(b: &mut Bencher) {
+    let board = Board::new();
+    b.iter(|| {
+        let mut board = board.clone();
+        board.make_move(Stone::Black, (3, 3))
+    });
+}
+
+fn make_move_capture(b: &mut Bencher) {
+    // White at (18, 2) captures the black stone in atari at (17, 2).
+    let board = Board::from_str(MIDGAME);
+    b.iter(|| {
+        let mut board = board.clone();
+        board.make_move(Stone::White, (18, 2))
+    });
+}
+
+fn liberties(b: &mut Bencher) {
+    let board = Board::from_str(MIDGAME);
+    b.iter(|| board.liberties((13, 2)));
+}
+
+fn chain_at(b: &mut Bencher) {
+    let board = Board::from_str(MIDGAME);
+    b.iter(|| board.chain_at((13, 2)));
+}
+
+fn playout_9x9(b: &mut Bencher) {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    b.iter(|| playout(9, &mut rng));
+}
+
+fn playout_19x19(b: &mut Bencher) {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    b.iter(|| playout(19, &mut rng));
+}
+
+benchmark_group!(benches,
+    make_move_empty,
+    make_move_capture,
+    liberties,
+    chain_at,
+    playout_9x9,
+    playout_19x19);
+benchmark_main!(benches);
@@ -0,0 +1,39 @@
+#[macro_use]
+extern crate bencher;
+extern crate joseki;
+
+use bencher::Bencher;
+
+use joseki::Game;
+
+fn replay(b: &mut Bencher, path: &str) {
+    b.iter(|| Game::from_sgf(path));
+}
+
+fn replay_game_1(b: &mut Bencher) {
+    replay(b, "tests/games/AlphaGo-Lee_Sedol-1.sgf");
+}
+
+fn replay_game_2(b: &mut Bencher) {
+    replay(b, "tests/games/AlphaGo-Lee_Sedol-2.sgf");
+}
+
+fn replay_game_3(b: &mut Bencher) {
+    replay(b, "tests/games/AlphaGo-Lee_Sedol-3.sgf");
+}
+
+fn replay_game_4(b: &mut Bencher) {
+    replay(b, "tests/games/AlphaGo-Lee_Sedol-4.sgf");
+}
+
+fn replay_game_5(b: &mut Bencher) {
+    replay(b, "tests/games/AlphaGo-Lee_Sedol-5.sgf");
+}
+
+benchmark_group!(benches,
+    replay_game_1,
+    replay_game_2,
+    replay_game_3,
+    replay_game_4,
+    replay_game_5);
+benchmark_main!(benches);
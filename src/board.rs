@@ -1,11 +1,20 @@
 #![allow(dead_code)]
 use std::collections::HashSet;
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::error::Error;
 use std::fmt;
 use std::ops::{Index, IndexMut};
 
+use point::Point;
+use text::BoardFormatter;
+
 const DEFAULT_BOARD_SIZE: usize = 19;
 
+/// The contents of a single point on the board.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Stone {
     Empty,
     Black,
@@ -21,7 +30,8 @@ impl Stone {
         }
     }
 
-    fn not(&self) -> Stone {
+    /// Returns the stone of the opposing color (`Empty` is its own opposite).
+    pub fn not(&self) -> Stone {
         match *self {
             Stone::Empty => Stone::Empty,
             Stone::Black => Stone::White,
@@ -36,8 +46,52 @@ impl fmt::Display for Stone {
     }
 }
 
+/// The reason a move was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IllegalMove {
+    /// The move is an attempt to place `Stone::Empty`.
+    EmptyStone,
+    /// The point lies outside of the board.
+    OutOfBounds,
+    /// There is already a stone at the point.
+    Occupied,
+    /// The stone would have no liberties and wouldn't capture anything.
+    Suicide,
+    /// The move would immediately recapture a ko, repeating the previous position.
+    Ko,
+    /// The move would repeat an earlier board position (positional superko).
+    Superko,
+}
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match *self {
+            IllegalMove::EmptyStone => "cannot place an empty stone",
+            IllegalMove::OutOfBounds => "point is outside of the board",
+            IllegalMove::Occupied => "point is already occupied",
+            IllegalMove::Suicide => "move would be suicide",
+            IllegalMove::Ko => "move would retake a ko",
+            IllegalMove::Superko => "move would repeat an earlier position",
+        };
+
+        write!(f, "{}", reason)
+    }
+}
+
+impl Error for IllegalMove {}
+
 
+/// A square Go board, without any history (see `Game` for ko handling).
+///
+/// With the `serde` feature enabled, a board is serialized as its size and a list of rows, using
+/// `#` for black stones, `O` for white stones and `.` for empty points:
+///
+/// ```json
+/// { "size": 3, "rows": [".#.", "#O#", ".#."] }
+/// ```
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "BoardRows", into = "BoardRows"))]
 pub struct Board {
     state: Vec<Stone>,
     pub size: usize,
@@ -58,6 +112,7 @@ impl Board {
     }
 
     /// Creates a new `Board` from a string representation of the board state.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(board: &str) -> Board {
         let state: Vec<_> = board.chars()
             .filter(|c| !c.is_whitespace())
@@ -73,106 +128,145 @@ impl Board {
         Board { state, size }
     }
 
-    /// Returns true if placing `stone` at `x, y` is a valid play. The ko rule is handled at the
-    /// `Game` level, since `Board` doesn't store previous state.
-    fn legal_move(&mut self, stone: Stone, x: usize, y: usize) -> bool {
+    /// Returns the stone at `point`, or `None` if `point` lies outside of the board.
+    pub fn get<P: Into<Point>>(&self, point: P) -> Option<Stone> {
+        let point = point.into();
+
+        if point.in_bounds(self.size) {
+            Some(self[point])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the stone at `point`, or `None` if `point` lies outside of
+    /// the board.
+    pub fn get_mut<P: Into<Point>>(&mut self, point: P) -> Option<&mut Stone> {
+        let point = point.into();
+
+        if point.in_bounds(self.size) {
+            Some(&mut self[point])
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether placing `stone` at `point` is a valid play, returning the reason if it
+    /// isn't. The ko rule is handled at the `Game` level, since `Board` doesn't store previous
+    /// state.
+    pub fn check_move<P: Into<Point>>(&self, stone: Stone, point: P) -> Result<(), IllegalMove> {
+        let point = point.into();
+
         if stone == Stone::Empty {
-            return false;
-        } else if self[(x, y)] != Stone::Empty {
-            return false;
-        } else if (x >= self.size) || (y >= self.size) {
-            return false;
+            return Err(IllegalMove::EmptyStone);
+        } else if !point.in_bounds(self.size) {
+            return Err(IllegalMove::OutOfBounds);
+        } else if self[point] != Stone::Empty {
+            return Err(IllegalMove::Occupied);
         }
 
-        // See if placing stone would cause a capture (perform before self-capture check).
-        for (nx, ny) in self.neighbours(x, y) {
-            if self[(nx, ny)] == stone.not() {
-                let liberties = self.liberties(nx, ny);
-                if liberties.len() == 1 && *liberties.iter().next().unwrap() == (x, y) {
-                    return true;
+        for neighbour in self.neighbours(point) {
+            if self[neighbour] == Stone::Empty {
+                // The stone will have at least one liberty after being placed.
+                return Ok(());
+            }
+
+            let liberties = self.liberties(neighbour);
+
+            if self[neighbour] == stone.not() {
+                // Placing the stone would capture the neighbouring chain, freeing up liberties.
+                if liberties.len() == 1 && liberties.contains(&point) {
+                    return Ok(());
                 }
+            } else if liberties.len() > 1 {
+                // Connecting to a friendly chain that has liberties other than `point`.
+                return Ok(());
             }
         }
 
-        // Prevent self-capture by simulating placing a stone at `(x, y)` and checking liberties.
-        self[(x, y)] = stone;
-        let liberties = self.liberties(x, y);
-        self[(x, y)] = Stone::Empty;
+        Err(IllegalMove::Suicide)
+    }
 
-        if liberties.len() == 0 {
-            return false;
-        }
+    /// Returns true if placing `stone` at `point` is a valid play. The ko rule is handled at the
+    /// `Game` level, since `Board` doesn't store previous state.
+    pub fn legal_move<P: Into<Point>>(&self, stone: Stone, point: P) -> bool {
+        self.check_move(stone, point).is_ok()
+    }
 
-        true
+    /// Returns an iterator over every point where `stone` can legally be played.
+    pub fn legal_moves<'a>(&'a self, stone: Stone) -> impl Iterator<Item = Point> + 'a {
+        self.points().filter(move |&point| self.legal_move(stone, point))
     }
 
-    /// Places `stone` at `(x, y)`, returning true if it was successful. Handles captures.
-    pub fn make_move(&mut self, stone: Stone, x: usize, y: usize) -> bool {
-        if self[(x, y)] != Stone::Empty {
-            return false;
-        } else if stone == Stone::Empty {
-            return false;
-        } else if !self.legal_move(stone, x, y) {
-            return false;
-        }
+    /// Places `stone` at `point`, returning true if it was successful. Handles captures.
+    pub fn make_move<P: Into<Point>>(&mut self, stone: Stone, point: P) -> bool {
+        self.try_move(stone, point).is_ok()
+    }
+
+    /// Places `stone` at `point`, returning the reason the move is illegal if it wasn't
+    /// successful. The board is left untouched if the move is illegal. Handles captures.
+    pub fn try_move<P: Into<Point>>(&mut self, stone: Stone, point: P) -> Result<(), IllegalMove> {
+        let point = point.into();
+        self.check_move(stone, point)?;
 
         let opposing_stone = stone.not();
 
-        for (nx, ny) in self.neighbours(x, y) {
-            if self[(nx, ny)] == opposing_stone {
-                // If the chain that `(nx, ny)` is a part of only has a single liberty at point
-                // `(x, y)`, then the entire chain will be captured by making this move.
-                let liberties = self.liberties(nx, ny);
+        for neighbour in self.neighbours(point) {
+            if self[neighbour] == opposing_stone {
+                // If the chain that `neighbour` is a part of only has a single liberty at
+                // `point`, then the entire chain will be captured by making this move.
+                let liberties = self.liberties(neighbour);
 
-                if liberties.len() == 1 && *liberties.iter().next().unwrap() == (x, y) {
-                    for (cx, cy) in self.chain_at(nx, ny) {
-                        self[(cx, cy)] = Stone::Empty;
+                if liberties.len() == 1 && liberties.contains(&point) {
+                    for captured in self.chain_at(neighbour) {
+                        self[captured] = Stone::Empty;
                     }
                 }
             }
         }
 
-        // Finally, place the stone at `(x, y)`.
-        self[(x, y)] = stone;
+        // Finally, place the stone at `point`.
+        self[point] = stone;
 
-        true
+        Ok(())
     }
 
-    /// Returns the positions adjacent to `(x, y)`.
-    fn neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+    /// Returns the positions adjacent to `point`.
+    pub fn neighbours<P: Into<Point>>(&self, point: P) -> Vec<Point> {
+        let Point { x, y } = point.into();
         let mut positions = Vec::new();
 
-        if x > 0 { positions.push((x - 1, y)) }
-        if y > 0 { positions.push((x, y - 1)) }
-        if x < (self.size - 1) { positions.push((x + 1, y)) }
-        if y < (self.size - 1) { positions.push((x, y + 1)) }
+        if x > 0 { positions.push(Point::new(x - 1, y)) }
+        if y > 0 { positions.push(Point::new(x, y - 1)) }
+        if x < (self.size - 1) { positions.push(Point::new(x + 1, y)) }
+        if y < (self.size - 1) { positions.push(Point::new(x, y + 1)) }
 
         positions
     }
 
-    /// Returns the set of all positions connected to the stone at `(x, y)`.
-    fn chain_at(&self, x: usize, y: usize) -> HashSet<(usize, usize)> {
+    /// Returns the set of all positions connected to the stone at `point`.
+    pub fn chain_at<P: Into<Point>>(&self, point: P) -> HashSet<Point> {
+        let point = point.into();
         let mut seen = HashSet::new();
-        let stone = self[(x, y)];
+        let stone = self[point];
 
         if stone == Stone::Empty {
             return seen;
         }
 
-        seen.insert((x, y));
+        seen.insert(point);
 
-        // Perform depth-first search starting from `(x, y)`
-        let mut horizon: Vec<_> = self.neighbours(x, y).into_iter()
-            .filter(|&(x, y)| self[(x, y)] == stone)
+        // Perform depth-first search starting from `point`
+        let mut horizon: Vec<_> = self.neighbours(point).into_iter()
+            .filter(|&p| self[p] == stone)
             .collect();
 
-        while horizon.len() > 0 {
-            let (nx, ny) = horizon.pop().expect("horizon is empty");
-            seen.insert((nx, ny));
+        while let Some(next) = horizon.pop() {
+            seen.insert(next);
 
-            for (a, b) in self.neighbours(nx, ny) {
-                if self[(a, b)] == stone && !seen.contains(&(a, b)) {
-                    horizon.push((a, b));
+            for neighbour in self.neighbours(next) {
+                if self[neighbour] == stone && !seen.contains(&neighbour) {
+                    horizon.push(neighbour);
                 }
             }
         }
@@ -180,33 +274,33 @@ impl Board {
         seen
     }
 
-    /// Returns the set of liberties of the stone at `(x, y)`.
-    fn liberties(&self, x: usize, y: usize) -> HashSet<(usize, usize)> {
+    /// Returns the set of liberties of the stone at `point`.
+    pub fn liberties<P: Into<Point>>(&self, point: P) -> HashSet<Point> {
+        let point = point.into();
         let mut liberties = HashSet::new();
         let mut seen = HashSet::new();
-        let stone = self[(x, y)];
+        let stone = self[point];
 
         if stone == Stone::Empty {
             return liberties;
         }
 
-        seen.insert((x, y));
+        seen.insert(point);
 
-        // Perform depth-first search starting from `(x, y)`
-        let mut horizon: Vec<_> = self.neighbours(x, y).into_iter().collect();
+        // Perform depth-first search starting from `point`
+        let mut horizon: Vec<_> = self.neighbours(point).into_iter().collect();
 
-        while horizon.len() > 0 {
-            let (nx, ny) = horizon.pop().expect("horizon is empty");
-            seen.insert((nx, ny));
+        while let Some(next) = horizon.pop() {
+            seen.insert(next);
 
-            if self[(nx, ny)] == Stone::Empty {
-                liberties.insert((nx, ny));
+            if self[next] == Stone::Empty {
+                liberties.insert(next);
             }
 
-            if self[(nx, ny)] == stone {
-                for (a, b) in self.neighbours(nx, ny) {
-                    if !seen.contains(&(a, b)) {
-                        horizon.push((a, b));
+            if self[next] == stone {
+                for neighbour in self.neighbours(next) {
+                    if !seen.contains(&neighbour) {
+                        horizon.push(neighbour);
                     }
                 }
             }
@@ -215,8 +309,49 @@ impl Board {
         liberties
     }
 
-    /// Returns true if (x, y) is a star point (hoshi) based on the current board size.
-    fn star_point(&self, x: usize, y: usize) -> bool {
+    /// Returns the chain containing the stone at `point`, or `None` if `point` is empty.
+    pub fn chain<P: Into<Point>>(&self, point: P) -> Option<Chain> {
+        let point = point.into();
+        let color = self[point];
+
+        if color == Stone::Empty {
+            return None;
+        }
+
+        Some(Chain {
+            color,
+            stones: self.chain_at(point),
+            liberties: self.liberties(point),
+        })
+    }
+
+    /// Returns an iterator over every chain on the board, in order of their top-left-most stone.
+    pub fn chains(&self) -> Chains<'_> {
+        Chains {
+            board: self,
+            points: self.points(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Returns an iterator over every point on the board, row by row.
+    pub fn points(&self) -> Points {
+        Points { size: self.size, index: 0 }
+    }
+
+    /// Returns the offset of `point` into `state`, panicking if it lies outside of the board
+    /// (rather than silently wrapping around to the next row).
+    fn offset(&self, point: Point) -> usize {
+        assert!(point.in_bounds(self.size),
+            "point {} is outside of the {}x{} board", point, self.size, self.size);
+
+        point.y * self.size + point.x
+    }
+
+    /// Returns true if `point` is a star point (hoshi) based on the current board size.
+    pub fn star_point<P: Into<Point>>(&self, point: P) -> bool {
+        let Point { x, y } = point.into();
+
         match self.size {
             9 => (x == 4 && y == 4) || ((x == 2 || x == 6) && (y == 2 || y == 6)),
             13 => (x == 6 && y == 6) || ((x == 3 || x == 9) && (y == 3 || y == 9)),
@@ -226,54 +361,175 @@ impl Board {
     }
 }
 
+/// A maximal group of orthogonally connected stones of the same color.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chain {
+    color: Stone,
+    stones: HashSet<Point>,
+    liberties: HashSet<Point>,
+}
+
+impl Chain {
+    /// Returns the color of the stones in the chain.
+    pub fn color(&self) -> Stone {
+        self.color
+    }
+
+    /// Returns the positions of the stones in the chain.
+    pub fn stones(&self) -> &HashSet<Point> {
+        &self.stones
+    }
+
+    /// Returns the empty positions adjacent to the chain.
+    pub fn liberties(&self) -> &HashSet<Point> {
+        &self.liberties
+    }
+
+    /// Returns the number of stones in the chain.
+    pub fn len(&self) -> usize {
+        self.stones.len()
+    }
+
+    /// Returns true if the chain has no stones (never the case for chains returned by `Board`).
+    pub fn is_empty(&self) -> bool {
+        self.stones.is_empty()
+    }
+
+    /// Returns true if the chain has exactly one liberty left.
+    pub fn in_atari(&self) -> bool {
+        self.liberties.len() == 1
+    }
+}
+
+/// Iterator over the points of a `Board`, created by `Board::points`.
+#[derive(Clone, Debug)]
+pub struct Points {
+    size: usize,
+    index: usize,
+}
+
+impl Iterator for Points {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.index >= self.size * self.size {
+            return None;
+        }
+
+        let point = Point::new(self.index % self.size, self.index / self.size);
+        self.index += 1;
+
+        Some(point)
+    }
+}
+
+/// Iterator over the chains of a `Board`, created by `Board::chains`.
+#[derive(Clone, Debug)]
+pub struct Chains<'a> {
+    board: &'a Board,
+    points: Points,
+    seen: HashSet<Point>,
+}
+
+impl<'a> Iterator for Chains<'a> {
+    type Item = Chain;
+
+    fn next(&mut self) -> Option<Chain> {
+        for point in self.points.by_ref() {
+            if self.seen.contains(&point) {
+                continue;
+            }
+
+            if let Some(chain) = self.board.chain(point) {
+                self.seen.extend(chain.stones.iter().cloned());
+                return Some(chain);
+            }
+        }
+
+        None
+    }
+}
+
 impl Default for Board {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Index<(usize, usize)> for Board {
+/// Serialized form of a `Board` (see the `Board` documentation for the schema).
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct BoardRows {
+    size: usize,
+    rows: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Board> for BoardRows {
+    fn from(board: Board) -> BoardRows {
+        let rows = board.state.chunks(board.size)
+            .map(|row| row.iter().map(|stone| match *stone {
+                Stone::Empty => '.',
+                Stone::Black => '#',
+                Stone::White => 'O',
+            }).collect())
+            .collect();
+
+        BoardRows { size: board.size, rows }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BoardRows> for Board {
+    type Error = String;
+
+    fn try_from(rows: BoardRows) -> Result<Board, String> {
+        if rows.rows.len() != rows.size {
+            return Err(format!("expected {} rows, found {}", rows.size, rows.rows.len()));
+        }
+
+        let mut board = Board::with_size(rows.size);
+
+        for (y, row) in rows.rows.iter().enumerate() {
+            if row.chars().count() != rows.size {
+                return Err(format!("expected row {} to have {} points", y, rows.size));
+            }
+
+            for (x, c) in row.chars().enumerate() {
+                board[(x, y)] = match c {
+                    '.' => Stone::Empty,
+                    '#' => Stone::Black,
+                    'O' => Stone::White,
+                    _ => return Err(format!("invalid point '{}' in row {}", c, y)),
+                };
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+impl<P: Into<Point>> Index<P> for Board {
     type Output = Stone;
 
-    fn index<'a>(&'a self, index: (usize, usize)) -> &'a Stone {
-        let (x, y) = index;
-        &self.state[y * self.size + x]
+    /// Panics if `index` lies outside of the board (see `Board::get` for a checked variant).
+    fn index(&self, index: P) -> &Stone {
+        let offset = self.offset(index.into());
+        &self.state[offset]
     }
 }
 
-impl IndexMut<(usize, usize)> for Board {
-    fn index_mut<'a>(&'a mut self, index: (usize, usize)) -> &'a mut Stone {
-        let (x, y) = index;
-        &mut self.state[y * self.size + x]
+impl<P: Into<Point>> IndexMut<P> for Board {
+    /// Panics if `index` lies outside of the board (see `Board::get_mut` for a checked variant).
+    fn index_mut(&mut self, index: P) -> &mut Stone {
+        let offset = self.offset(index.into());
+        &mut self.state[offset]
     }
 }
 
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut rows = Vec::new();
-
-        for y in 0..self.size {
-            let mut row = String::new();
-
-            for x in 0..self.size {
-                match self.state[y * self.size + x] {
-                    Stone::Empty => {
-                        if self.star_point(x, y) {
-                            row.push('•'); // U+2022 BULLET
-                        } else {
-                            row.push(Stone::Empty.char());
-                        }
-                    }
-                    stone => row.push(stone.char()),
-                }
-                row.push(' ');
-            }
-
-            row.pop(); // remove trailing space from row
-            rows.push(row);
-        }
-
-        write!(f, "{}", rows.join("\n"))
+        write!(f, "{}", BoardFormatter::new().format(self))
     }
 }
 
@@ -283,7 +539,8 @@ mod tests {
     use std::collections::HashSet;
     use std::iter::FromIterator;
 
-    use super::{Board, Stone};
+    use super::{Board, Chain, IllegalMove, Stone};
+    use point::Point;
 
     #[test]
     fn empty_board() {
@@ -332,16 +589,16 @@ mod tests {
     #[should_panic]
     fn access_invalid_position() {
         let board = Board::new();
-        board[(20, 20)];
+        let _ = board[(20, 20)];
     }
 
     #[test]
     fn position_neighbours() {
         let board = Board::with_size(3);
-        assert_eq!(board.neighbours(0, 0).len(), 2);
-        assert_eq!(board.neighbours(0, 1).len(), 3);
-        assert_eq!(board.neighbours(1, 1).len(), 4);
-        assert_eq!(board.neighbours(2, 2).len(), 2);
+        assert_eq!(board.neighbours((0, 0)).len(), 2);
+        assert_eq!(board.neighbours((0, 1)).len(), 3);
+        assert_eq!(board.neighbours((1, 1)).len(), 4);
+        assert_eq!(board.neighbours((2, 2)).len(), 2);
     }
 
     #[test]
@@ -353,18 +610,18 @@ mod tests {
             O.O#O \
             .#OO.");
 
-        assert_eq!(board.chain_at(0, 0).len(), 0);
+        assert_eq!(board.chain_at((0, 0)).len(), 0);
 
-        let chain_1 = board.chain_at(0, 1);
+        let chain_1 = board.chain_at((0, 1));
         assert_eq!(chain_1.len(), 3);
-        assert_eq!(chain_1, board.chain_at(1, 1));
-        assert_eq!(chain_1, board.chain_at(1, 0));
+        assert_eq!(chain_1, board.chain_at((1, 1)));
+        assert_eq!(chain_1, board.chain_at((1, 0)));
 
-        let chain_2 = board.chain_at(2, 2);
+        let chain_2 = board.chain_at((2, 2));
         assert_eq!(chain_2.len(), 4);
-        assert_eq!(chain_2, board.chain_at(2, 3));
-        assert_eq!(chain_2, board.chain_at(2, 4));
-        assert_eq!(chain_2, board.chain_at(3, 4));
+        assert_eq!(chain_2, board.chain_at((2, 3)));
+        assert_eq!(chain_2, board.chain_at((2, 4)));
+        assert_eq!(chain_2, board.chain_at((3, 4)));
     }
 
     #[test]
@@ -376,14 +633,14 @@ mod tests {
             OO### \
             .O.O#");
 
-        let chain_1 = board.liberties(0, 3);
-        let expected_1 = HashSet::from_iter(vec![(0, 1), (0, 4), (2, 4)]);
-        assert_eq!(chain_1, board.liberties(1, 3));
+        let chain_1 = board.liberties((0, 3));
+        let expected_1 = HashSet::from_iter(vec![Point::new(0, 1), Point::new(0, 4), Point::new(2, 4)]);
+        assert_eq!(chain_1, board.liberties((1, 3)));
         assert_eq!(chain_1, expected_1);
 
-        let chain_2 = board.liberties(2, 3);
-        let expected_2 = HashSet::from_iter(vec![(2, 2), (2, 4), (4, 2)]);
-        assert_eq!(chain_2, board.liberties(4, 4));
+        let chain_2 = board.liberties((2, 3));
+        let expected_2 = HashSet::from_iter(vec![Point::new(2, 2), Point::new(2, 4), Point::new(4, 2)]);
+        assert_eq!(chain_2, board.liberties((4, 4)));
         assert_eq!(chain_2, expected_2)
     }
 
@@ -399,7 +656,7 @@ mod tests {
             #.# \
             .#.");
 
-        assert!(board.make_move(Stone::Black, 1, 0));
+        assert!(board.make_move(Stone::Black, (1, 0)));
         assert_eq!(board, expected);
     }
 
@@ -410,8 +667,8 @@ mod tests {
             .B. \
             ...");
 
-        assert!(!board.make_move(Stone::Black, 1, 1));
-        assert!(!board.make_move(Stone::White, 1, 1));
+        assert!(!board.make_move(Stone::Black, (1, 1)));
+        assert!(!board.make_move(Stone::White, (1, 1)));
     }
 
     #[test]
@@ -426,7 +683,7 @@ mod tests {
             #.# \
             .#.");
 
-        assert!(board.make_move(Stone::Black, 1, 0));
+        assert!(board.make_move(Stone::Black, (1, 0)));
         assert_eq!(board, expected);
     }
 
@@ -446,7 +703,7 @@ mod tests {
             ..... \
             .....");
 
-        assert!(board.make_move(Stone::Black, 0, 2));
+        assert!(board.make_move(Stone::Black, (0, 2)));
         assert_eq!(board, expected);
     }
 
@@ -466,7 +723,7 @@ mod tests {
             #OO.. \
             .....");
 
-        assert!(board.make_move(Stone::White, 0, 2));
+        assert!(board.make_move(Stone::White, (0, 2)));
         assert_eq!(board, expected);
     }
 
@@ -486,7 +743,7 @@ mod tests {
             .O##. \
             .....");
 
-        assert!(board.make_move(Stone::Black, 2, 1));
+        assert!(board.make_move(Stone::Black, (2, 1)));
         assert_eq!(board, expected);
     }
 
@@ -499,7 +756,7 @@ mod tests {
 
         let expected = board.clone();
 
-        assert!(!board.make_move(Stone::White, 1, 1));
+        assert!(!board.make_move(Stone::White, (1, 1)));
         assert_eq!(board, expected);
     }
 
@@ -514,7 +771,7 @@ mod tests {
 
         let expected = board.clone();
 
-        assert!(!board.make_move(Stone::Black, 3, 3));
+        assert!(!board.make_move(Stone::Black, (3, 3)));
         assert_eq!(board, expected);
     }
 
@@ -534,7 +791,174 @@ mod tests {
             OOO.. \
             .....");
 
-        assert!(board.make_move(Stone::White, 1, 1));
+        assert!(board.make_move(Stone::White, (1, 1)));
         assert_eq!(board, expected);
     }
+
+    #[test]
+    fn legal_moves() {
+        let board = Board::from_str("\
+            .#O.. \
+            #.#O. \
+            .#O.. \
+            ..... \
+            .....");
+
+        assert!(board.legal_move(Stone::Black, (4, 4)));
+        assert!(board.legal_move(Stone::Black, (1, 1)));
+        // White captures the black stone at (2, 1), so this isn't self-capture.
+        assert!(board.legal_move(Stone::White, (1, 1)));
+        assert!(!board.legal_move(Stone::White, (0, 0)));
+        assert!(board.legal_move(Stone::Black, (0, 0)));
+        assert!(!board.legal_move(Stone::Black, (1, 0)));
+        assert!(!board.legal_move(Stone::Empty, (4, 4)));
+    }
+
+    #[test]
+    fn chain_at_point() {
+        let board = Board::from_str("\
+            .#..# \
+            ##.O# \
+            ..O.. \
+            O.O#O \
+            .#OO.");
+
+        assert_eq!(board.chain((0, 0)), None);
+
+        let chain = board.chain((1, 1)).unwrap();
+        assert_eq!(chain.color(), Stone::Black);
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain.stones(), &board.chain_at((1, 1)));
+        assert_eq!(chain.liberties(), &board.liberties((1, 1)));
+        assert!(!chain.in_atari());
+
+        let chain = board.chain((3, 3)).unwrap();
+        assert_eq!(chain.color(), Stone::Black);
+        assert_eq!(chain.len(), 1);
+        assert!(chain.in_atari());
+    }
+
+    #[test]
+    fn all_chains() {
+        let board = Board::from_str("\
+            .#..# \
+            ##.O# \
+            ..O.. \
+            O.O#O \
+            .#OO.");
+
+        let chains: Vec<Chain> = board.chains().collect();
+        let stones: usize = chains.iter().map(|c| c.len()).sum();
+
+        assert_eq!(chains.len(), 8);
+        assert_eq!(stones, 14);
+        assert_eq!(chains[0], board.chain((1, 0)).unwrap());
+        assert_eq!(chains.iter().filter(|c| c.color() == Stone::White).count(), 4);
+        assert_eq!(Board::with_size(9).chains().count(), 0);
+    }
+
+    #[test]
+    fn all_points() {
+        let board = Board::with_size(3);
+        let points: Vec<Point> = board.points().collect();
+
+        assert_eq!(points.len(), 9);
+        assert_eq!(points[0], Point::new(0, 0));
+        assert_eq!(points[1], Point::new(1, 0));
+        assert_eq!(points[8], Point::new(2, 2));
+    }
+
+    #[test]
+    fn illegal_move_reasons() {
+        let board = Board::from_str("\
+            .#O.. \
+            #.#O. \
+            .#O.. \
+            ..... \
+            .....");
+
+        assert_eq!(board.check_move(Stone::Black, (4, 4)), Ok(()));
+        assert_eq!(board.check_move(Stone::White, (1, 1)), Ok(()));
+        assert_eq!(board.check_move(Stone::Empty, (4, 4)), Err(IllegalMove::EmptyStone));
+        assert_eq!(board.check_move(Stone::Black, (5, 0)), Err(IllegalMove::OutOfBounds));
+        assert_eq!(board.check_move(Stone::Black, (0, 5)), Err(IllegalMove::OutOfBounds));
+        assert_eq!(board.check_move(Stone::Black, (1, 0)), Err(IllegalMove::Occupied));
+        assert_eq!(board.check_move(Stone::White, (0, 0)), Err(IllegalMove::Suicide));
+    }
+
+    #[test]
+    fn legal_move_list() {
+        let board = Board::from_str("\
+            .#. \
+            #.# \
+            .#O");
+
+        let black: Vec<Point> = board.legal_moves(Stone::Black).collect();
+
+        assert_eq!(black, vec![Point::new(0, 0), Point::new(2, 0), Point::new(1, 1), Point::new(0, 2)]);
+        assert_eq!(board.legal_moves(Stone::White).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn access_wrapped_position() {
+        // (20, 0) would alias (1, 1) if the row wasn't bounds-checked.
+        let board = Board::new();
+        let _ = board[(20, 0)];
+    }
+
+    #[test]
+    fn checked_access() {
+        let mut board = Board::with_size(9);
+
+        assert_eq!(board.get((8, 8)), Some(Stone::Empty));
+        assert_eq!(board.get((9, 0)), None);
+        assert_eq!(board.get((0, 9)), None);
+
+        *board.get_mut((4, 4)).unwrap() = Stone::Black;
+        assert_eq!(board.get((4, 4)), Some(Stone::Black));
+        assert!(board.get_mut((10, 1)).is_none());
+    }
+
+    #[test]
+    fn checked_moves() {
+        let mut board = Board::from_str("\
+            .!. \
+            #O# \
+            .#.");
+
+        let expected = Board::from_str("\
+            .#. \
+            #.# \
+            .#.");
+
+        assert_eq!(board.try_move(Stone::White, (3, 0)), Err(IllegalMove::OutOfBounds));
+        assert_eq!(board.try_move(Stone::White, (0, 3)), Err(IllegalMove::OutOfBounds));
+        assert_eq!(board.try_move(Stone::Black, (1, 1)), Err(IllegalMove::Occupied));
+        assert_eq!(board.try_move(Stone::Black, (1, 0)), Ok(()));
+        assert_eq!(board, expected);
+
+        assert_eq!(board.try_move(Stone::White, (1, 1)), Err(IllegalMove::Suicide));
+        assert_eq!(board, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_board() {
+        use serde_json;
+
+        let board = Board::from_str("\
+            .#. \
+            #O# \
+            .#.");
+
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(json, r##"{"size":3,"rows":[".#.","#O#",".#."]}"##);
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+
+        assert_eq!(serde_json::to_string(&Stone::White).unwrap(), r##""white""##);
+        assert!(serde_json::from_str::<Board>(r##"{"size":2,"rows":[".#"]}"##).is_err());
+        assert!(serde_json::from_str::<Board>(r##"{"size":2,"rows":[".#","#"]}"##).is_err());
+        assert!(serde_json::from_str::<Board>(r##"{"size":2,"rows":[".#","#X"]}"##).is_err());
+    }
 }
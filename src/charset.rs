@@ -0,0 +1,110 @@
+use encoding_rs::{Encoding, EUC_KR, GBK, UTF_8, WINDOWS_1252};
+use regex::bytes::Regex;
+
+/// Character sets tried, in order, for records that can't declare one. GIB and NGF records
+/// come from Korean and Chinese servers.
+const GUESSES: [&Encoding; 2] = [EUC_KR, GBK];
+
+/// Returns the character set declared by the `CA` property of an SGF file, if there is one.
+pub fn sgf_charset(bytes: &[u8]) -> Option<String> {
+    let re = Regex::new(r"CA\s*\[([^\]]*)\]").expect("invalid regex");
+
+    re.captures(bytes)
+        .map(|cap| String::from_utf8_lossy(&cap[1]).trim().to_string())
+        .filter(|label| !label.is_empty())
+}
+
+/// Decodes the contents of a game record. Text that is valid UTF-8 is decoded as such, since
+/// records are often re-encoded without updating their declared character set (and text in
+/// other encodings is very unlikely to be valid UTF-8). Otherwise, the text is decoded using the
+/// `declared` character set, or as ISO-8859-1 (the SGF default) if it is missing or unknown.
+pub fn decode(bytes: &[u8], declared: Option<&str>) -> String {
+    let (text, _, had_errors) = UTF_8.decode(bytes);
+
+    if !had_errors {
+        return text.into_owned();
+    }
+
+    declared.and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(WINDOWS_1252)
+        .decode(bytes).0
+        .into_owned()
+}
+
+/// Decodes the contents of a game record that has no way to declare its character set. Text
+/// that is valid UTF-8 is decoded as such, and otherwise using the first of EUC-KR and GBK that
+/// it's valid in, or as ISO-8859-1 if neither fits. EUC-KR accepts most GBK text, so records
+/// known to be Chinese should be decoded with `decode` instead.
+pub fn guess(bytes: &[u8]) -> String {
+    let (text, _, had_errors) = UTF_8.decode(bytes);
+
+    if !had_errors {
+        return text.into_owned();
+    }
+
+    for encoding in &GUESSES {
+        let (text, had_errors) = encoding.decode_without_bom_handling(bytes);
+
+        if !had_errors {
+            return text.into_owned();
+        }
+    }
+
+    WINDOWS_1252.decode(bytes).0.into_owned()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, guess, sgf_charset};
+    use encoding_rs::{EUC_KR, GBK, SHIFT_JIS, WINDOWS_1252};
+
+    #[test]
+    fn charset_property() {
+        assert_eq!(sgf_charset(b"(;GM[1]CA[GB2312]SZ[19])"), Some("GB2312".to_string()));
+        assert_eq!(sgf_charset(b"(;GM[1]CA [ UTF-8 ])"), Some("UTF-8".to_string()));
+        assert_eq!(sgf_charset(b"(;GM[1]CA[])"), None);
+        assert_eq!(sgf_charset(b"(;GM[1]SZ[19])"), None);
+    }
+
+    #[test]
+    fn declared_charsets() {
+        for &(label, encoding, text) in &[
+            ("GB2312", GBK, "古力"),
+            ("EUC-KR", EUC_KR, "이세돌"),
+            ("Shift_JIS", SHIFT_JIS, "井山裕太"),
+            ("ISO-8859-1", WINDOWS_1252, "Müller"),
+        ] {
+            let (bytes, _, _) = encoding.encode(text);
+            assert_eq!(decode(&bytes, Some(label)), text);
+        }
+    }
+
+    #[test]
+    fn fallbacks() {
+        // Declared as EUC-KR, but actually UTF-8.
+        assert_eq!(decode("柯洁".as_bytes(), Some("EUC-KR")), "柯洁");
+
+        // Unknown labels are ignored.
+        assert_eq!(decode(b"M\xfcller", Some("not-a-charset")), "Müller");
+
+        // Without a declared charset, text that isn't UTF-8 is treated as ISO-8859-1.
+        assert_eq!(decode(b"M\xfcller", None), "Müller");
+
+        // Invalid sequences in the declared charset are replaced.
+        assert_eq!(decode(b"M\xfcller", Some("UTF-8")), "M\u{fffd}ller");
+
+        // A byte order mark overrides the declared charset.
+        assert_eq!(decode(b"\xef\xbb\xbfM\xc3\xbcller", Some("ISO-8859-1")), "Müller");
+    }
+
+    #[test]
+    fn guessed_charsets() {
+        assert_eq!(guess("柯洁".as_bytes()), "柯洁");
+        assert_eq!(guess(&EUC_KR.encode("이세돌").0), "이세돌");
+
+        // GBK text that isn't valid EUC-KR, and bytes that are valid in neither.
+        assert_eq!(guess(&GBK.encode("丂").0), "丂");
+        assert_eq!(guess(b"M\xfcller\xff"), "Müllerÿ");
+    }
+}
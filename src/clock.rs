@@ -0,0 +1,385 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use board::{Board, Stone};
+use regex::Regex;
+
+/// The fewest moves a player is assumed to have left to play, however full the board is.
+const MIN_MOVES_LEFT: u32 = 10;
+
+/// Time kept in reserve on every move, to allow for network lag.
+const LAG: Duration = Duration::from_millis(500);
+
+/// The kind of overtime that follows the main time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TimeControl {
+    /// No overtime: a player loses as soon as their main time runs out.
+    Absolute,
+    /// Japanese byo-yomi: each move must be played within `time`, and a period is used up each
+    /// time a move takes longer.
+    ByoYomi { periods: u32, time: Duration },
+    /// Canadian overtime: `stones` moves must be played within `time`, after which the period
+    /// starts again.
+    Canadian { stones: u32, time: Duration },
+    /// Fischer: `increment` is added to a player's time after each of their moves.
+    Fischer { increment: Duration },
+}
+
+impl TimeControl {
+    /// Parses an SGF overtime description (`OT`), such as `5x30 byo-yomi`, `25/600 Canadian` or
+    /// `10 fischer`. Returns `None` if the description isn't understood.
+    pub fn from_sgf(text: &str) -> Option<TimeControl> {
+        let seconds = |s: &str| Duration::try_from_secs_f64(s.parse().ok()?).ok();
+        let lower = text.trim().to_lowercase();
+
+        if lower.is_empty() || lower == "none" || lower == "absolute" {
+            return Some(TimeControl::Absolute);
+        }
+
+        let number = r"(\d+(?:\.\d+)?)";
+
+        if lower.contains("fischer") {
+            let re = Regex::new(number).expect("invalid regex");
+            let increment = seconds(&re.captures(&lower)?[1])?;
+
+            return Some(TimeControl::Fischer { increment });
+        }
+
+        let re = Regex::new(&format!(r"(\d+)\s*([x/])\s*{}", number)).expect("invalid regex");
+        let cap = re.captures(&lower)?;
+        let count = cap[1].parse().ok()?;
+        let time = seconds(&cap[3])?;
+
+        if &cap[2] == "/" || lower.contains("canadian") {
+            Some(TimeControl::Canadian { stones: count, time })
+        } else {
+            Some(TimeControl::ByoYomi { periods: count, time })
+        }
+    }
+
+    /// Returns the SGF overtime description (`OT`), or `None` for absolute time.
+    pub fn to_sgf(&self) -> Option<String> {
+        match *self {
+            TimeControl::Absolute => None,
+            TimeControl::ByoYomi { periods, time } => {
+                Some(format!("{}x{} byo-yomi", periods, time.as_secs_f64()))
+            },
+            TimeControl::Canadian { stones, time } => {
+                Some(format!("{}/{} Canadian", stones, time.as_secs_f64()))
+            },
+            TimeControl::Fischer { increment } => Some(format!("{} fischer", increment.as_secs_f64())),
+        }
+    }
+}
+
+/// The time limits of a game: a main time, followed by some kind of overtime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimeSettings {
+    pub main_time: Duration,
+    pub control: TimeControl,
+}
+
+impl TimeSettings {
+    /// Creates settings with only a main time (absolute time).
+    pub fn absolute(main_time: Duration) -> TimeSettings {
+        TimeSettings { main_time, control: TimeControl::Absolute }
+    }
+
+    /// Returns the time a player has at the start of the game.
+    pub fn initial(&self) -> TimeLeft {
+        TimeLeft { time: self.main_time, periods: None }
+    }
+
+    /// Returns the time a player has on entering overtime, or `None` if there is no overtime.
+    fn overtime(&self) -> Option<TimeLeft> {
+        match self.control {
+            TimeControl::ByoYomi { periods, time } => Some(TimeLeft { time, periods: Some(periods) }),
+            TimeControl::Canadian { stones, time } => Some(TimeLeft { time, periods: Some(stones) }),
+            TimeControl::Absolute | TimeControl::Fischer { .. } => None,
+        }
+    }
+}
+
+/// The time a player has left, as recorded in SGF by `BL`/`WL` and `OB`/`OW`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimeLeft {
+    /// The main time left or, in overtime, the time left in the current period.
+    pub time: Duration,
+    /// The byo-yomi periods or Canadian stones left, once the player is in overtime.
+    pub periods: Option<u32>,
+}
+
+/// The player that ran out of time, and so lost the game.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timeout(pub Stone);
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let player = if self.0 == Stone::Black { "black" } else { "white" };
+        write!(f, "{} ran out of time", player)
+    }
+}
+
+impl Error for Timeout {}
+
+/// A game clock, which counts down the time of each player as they make their moves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clock {
+    settings: TimeSettings,
+    black: TimeLeft,
+    white: TimeLeft,
+    timeout: Option<Stone>,
+}
+
+impl Clock {
+    /// Creates a clock with the full main time for both players.
+    pub fn new(settings: TimeSettings) -> Clock {
+        Clock {
+            settings,
+            black: settings.initial(),
+            white: settings.initial(),
+            timeout: None,
+        }
+    }
+
+    /// Returns the time limits the clock was created with.
+    pub fn settings(&self) -> &TimeSettings {
+        &self.settings
+    }
+
+    /// Returns the time `stone` has left.
+    pub fn time_left(&self, stone: Stone) -> TimeLeft {
+        if stone == Stone::White { self.white } else { self.black }
+    }
+
+    /// Sets the time `stone` has left, such as when resuming a game.
+    pub fn set_time_left(&mut self, stone: Stone, time_left: TimeLeft) {
+        *self.time_left_mut(stone) = time_left;
+    }
+
+    /// Returns the player that ran out of time, if either has.
+    pub fn timeout(&self) -> Option<Stone> {
+        self.timeout
+    }
+
+    /// Records that `stone` took `elapsed` to play a move, and starts their next period or adds
+    /// their increment. Once a player has run out of time, every move is a timeout.
+    pub fn record_move(&mut self, stone: Stone, elapsed: Duration) -> Result<(), Timeout> {
+        let left = self.check(stone, elapsed)?;
+        let settings = self.settings;
+
+        *self.time_left_mut(stone) = match (settings.control, left.periods) {
+            (TimeControl::ByoYomi { time, .. }, Some(periods)) => TimeLeft { time, periods: Some(periods) },
+            (TimeControl::Canadian { stones, time }, Some(periods)) if periods <= 1 => {
+                TimeLeft { time, periods: Some(stones) }
+            },
+            (TimeControl::Canadian { .. }, Some(periods)) => TimeLeft { periods: Some(periods - 1), ..left },
+            (TimeControl::Fischer { increment }, _) => TimeLeft { time: left.time.saturating_add(increment), ..left },
+            _ => left,
+        };
+
+        Ok(())
+    }
+
+    /// Checks whether `stone` has run out of time after thinking for `elapsed` without playing a
+    /// move, recording the timeout if they have. Otherwise, returns the time they would have
+    /// left (without changing the clock).
+    pub fn check(&mut self, stone: Stone, elapsed: Duration) -> Result<TimeLeft, Timeout> {
+        if let Some(stone) = self.timeout {
+            return Err(Timeout(stone));
+        }
+
+        let left = self.remaining(stone, elapsed);
+
+        if left.is_none() {
+            self.timeout = Some(stone);
+        }
+
+        left.ok_or(Timeout(stone))
+    }
+
+    /// Returns the time `stone` would have left after thinking for `elapsed`, or `None` if they
+    /// would run out of time.
+    fn remaining(&self, stone: Stone, elapsed: Duration) -> Option<TimeLeft> {
+        let mut left = self.time_left(stone);
+        let mut elapsed = elapsed;
+
+        loop {
+            if elapsed <= left.time {
+                left.time -= elapsed;
+                return Some(left);
+            }
+
+            elapsed -= left.time;
+
+            left = match left.periods {
+                // A byo-yomi period was used up, so the next one starts.
+                Some(periods) if periods > 1 => match self.settings.control {
+                    TimeControl::ByoYomi { time, .. } => TimeLeft { time, periods: Some(periods - 1) },
+                    _ => return None,
+                },
+                Some(_) => return None,
+                // The main time ran out, so overtime starts.
+                None => self.settings.overtime()?,
+            };
+        }
+    }
+
+    /// Returns how long `stone` should think about their next move on `board`. The remaining
+    /// main time is spread over the moves they're expected to have left (a quarter of the empty
+    /// points), on top of whatever overtime or increment each move is guaranteed.
+    pub fn allocate(&self, stone: Stone, board: &Board) -> Duration {
+        let left = self.time_left(stone);
+        let empty = board.points().filter(|&p| board[p] == Stone::Empty).count() as u32;
+        let moves_left = (empty / 4).max(MIN_MOVES_LEFT);
+
+        let (time, limit) = match (self.settings.control, left.periods) {
+            (TimeControl::ByoYomi { .. }, Some(_)) => (left.time, left.time),
+            (TimeControl::Canadian { .. }, Some(stones)) => (left.time / stones.max(1), left.time),
+            (control, _) => {
+                // Main time that runs out is followed by overtime, but not by the increment.
+                let (per_move, extra) = match control {
+                    TimeControl::Absolute => (Duration::default(), Duration::default()),
+                    TimeControl::ByoYomi { time, .. } => (time, time),
+                    TimeControl::Canadian { stones, time } => (time / stones.max(1), time / stones.max(1)),
+                    TimeControl::Fischer { increment } => (increment, Duration::default()),
+                };
+
+                (left.time / moves_left + per_move, left.time.saturating_add(extra))
+            },
+        };
+
+        time.min(limit.checked_sub(LAG).unwrap_or_default())
+    }
+
+    fn time_left_mut(&mut self, stone: Stone) -> &mut TimeLeft {
+        if stone == Stone::White { &mut self.white } else { &mut self.black }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Clock, TimeControl, TimeLeft, TimeSettings, Timeout};
+    use board::{Board, Stone};
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    fn settings(main_time: u64, control: TimeControl) -> TimeSettings {
+        TimeSettings { main_time: secs(main_time), control }
+    }
+
+    #[test]
+    fn absolute() {
+        let mut clock = Clock::new(TimeSettings::absolute(secs(60)));
+
+        assert_eq!(clock.record_move(Stone::Black, secs(40)), Ok(()));
+        assert_eq!(clock.time_left(Stone::Black), TimeLeft { time: secs(20), periods: None });
+        assert_eq!(clock.time_left(Stone::White), TimeLeft { time: secs(60), periods: None });
+
+        assert_eq!(clock.record_move(Stone::Black, secs(21)), Err(Timeout(Stone::Black)));
+        assert_eq!(clock.timeout(), Some(Stone::Black));
+
+        // The game is over once a player has run out of time.
+        assert_eq!(clock.record_move(Stone::White, secs(1)), Err(Timeout(Stone::Black)));
+    }
+
+    #[test]
+    fn byo_yomi() {
+        let mut clock = Clock::new(settings(60, TimeControl::ByoYomi { periods: 3, time: secs(30) }));
+
+        // Running out of main time uses up a period, and the next move starts a new one.
+        assert_eq!(clock.record_move(Stone::Black, secs(100)), Ok(()));
+        assert_eq!(clock.time_left(Stone::Black), TimeLeft { time: secs(30), periods: Some(2) });
+
+        // Moves within a period don't use it up.
+        assert_eq!(clock.record_move(Stone::Black, secs(29)), Ok(()));
+        assert_eq!(clock.time_left(Stone::Black), TimeLeft { time: secs(30), periods: Some(2) });
+
+        assert_eq!(clock.check(Stone::Black, secs(45)), Ok(TimeLeft { time: secs(15), periods: Some(1) }));
+        assert_eq!(clock.check(Stone::Black, secs(61)), Err(Timeout(Stone::Black)));
+    }
+
+    #[test]
+    fn canadian() {
+        let mut clock = Clock::new(settings(10, TimeControl::Canadian { stones: 3, time: secs(60) }));
+
+        assert_eq!(clock.record_move(Stone::White, secs(15)), Ok(()));
+        assert_eq!(clock.time_left(Stone::White), TimeLeft { time: secs(55), periods: Some(2) });
+
+        assert_eq!(clock.record_move(Stone::White, secs(20)), Ok(()));
+        assert_eq!(clock.time_left(Stone::White), TimeLeft { time: secs(35), periods: Some(1) });
+
+        // The last stone of a period starts the next one.
+        assert_eq!(clock.record_move(Stone::White, secs(30)), Ok(()));
+        assert_eq!(clock.time_left(Stone::White), TimeLeft { time: secs(60), periods: Some(3) });
+
+        assert_eq!(clock.record_move(Stone::White, secs(61)), Err(Timeout(Stone::White)));
+    }
+
+    #[test]
+    fn fischer() {
+        let mut clock = Clock::new(settings(60, TimeControl::Fischer { increment: secs(10) }));
+
+        assert_eq!(clock.record_move(Stone::Black, secs(5)), Ok(()));
+        assert_eq!(clock.time_left(Stone::Black), TimeLeft { time: secs(65), periods: None });
+        assert_eq!(clock.record_move(Stone::Black, secs(66)), Err(Timeout(Stone::Black)));
+    }
+
+    #[test]
+    fn allocate() {
+        let empty = Board::new();
+        let full = Board::from_str(&"#".repeat(19 * 19));
+
+        let clock = Clock::new(TimeSettings::absolute(secs(900)));
+        assert_eq!(clock.allocate(Stone::Black, &empty), secs(10));
+        assert_eq!(clock.allocate(Stone::Black, &full), secs(90));
+
+        // Byo-yomi is added to the main time, and used (less a margin) once it runs out.
+        let mut clock = Clock::new(settings(90, TimeControl::ByoYomi { periods: 5, time: secs(30) }));
+        assert_eq!(clock.allocate(Stone::Black, &empty), secs(31));
+        clock.record_move(Stone::Black, secs(100)).unwrap();
+        assert_eq!(clock.allocate(Stone::Black, &empty), Duration::from_millis(29500));
+
+        // Canadian overtime is split between the stones left in the period.
+        let mut clock = Clock::new(settings(0, TimeControl::Canadian { stones: 10, time: secs(100) }));
+        clock.set_time_left(Stone::White, TimeLeft { time: secs(40), periods: Some(4) });
+        assert_eq!(clock.allocate(Stone::White, &empty), secs(10));
+
+        // The increment is only added after the move, so it can't be spent on a move that would
+        // use up the main time.
+        let mut clock = Clock::new(settings(0, TimeControl::Fischer { increment: secs(5) }));
+        clock.set_time_left(Stone::Black, TimeLeft { time: secs(2), periods: None });
+        assert_eq!(clock.allocate(Stone::Black, &empty), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn overtime_descriptions() {
+        let byo_yomi = TimeControl::ByoYomi { periods: 5, time: secs(30) };
+        let canadian = TimeControl::Canadian { stones: 25, time: secs(600) };
+        let fischer = TimeControl::Fischer { increment: Duration::from_millis(2500) };
+
+        assert_eq!(TimeControl::from_sgf("5x30 byo-yomi"), Some(byo_yomi));
+        assert_eq!(TimeControl::from_sgf("5x30"), Some(byo_yomi));
+        assert_eq!(TimeControl::from_sgf("25/600 Canadian"), Some(canadian));
+        assert_eq!(TimeControl::from_sgf("Fischer 2.5"), Some(fischer));
+        assert_eq!(TimeControl::from_sgf(""), Some(TimeControl::Absolute));
+        assert_eq!(TimeControl::from_sgf("sudden death?"), None);
+        assert_eq!(TimeControl::from_sgf("5x99999999999999999999999 byo-yomi"), None);
+
+        for control in &[byo_yomi, canadian, fischer] {
+            assert_eq!(TimeControl::from_sgf(&control.to_sgf().unwrap()), Some(*control));
+        }
+
+        assert_eq!(TimeControl::Absolute.to_sgf(), None);
+    }
+}
@@ -0,0 +1,148 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::ops::Index;
+use std::path::Path;
+use std::slice;
+
+use charset;
+use game::Game;
+use sgf::{self, SgfOptions};
+
+/// The games in an SGF file, which may contain several game trees (such as a set of problems or
+/// the games of a tournament).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Collection {
+    pub games: Vec<Game>,
+}
+
+impl Collection {
+    /// Creates an empty collection.
+    pub fn new() -> Collection {
+        Collection { ..Default::default() }
+    }
+
+    /// Reads every game in an SGF file, decoded using the character set given by its `CA`
+    /// property.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Collection> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+
+        let declared = charset::sgf_charset(&bytes);
+        Ok(Collection::parse(&charset::decode(&bytes, declared.as_deref())))
+    }
+
+    /// Parses every game in the contents of an SGF file, following the main line of each game
+    /// tree.
+    pub fn parse(contents: &str) -> Collection {
+        Collection { games: sgf::parse(contents).iter().map(Game::from_tree).collect() }
+    }
+
+    /// Returns the number of games in the collection.
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Returns whether the collection has no games.
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    /// Returns an iterator over the games in the collection.
+    pub fn iter(&self) -> slice::Iter<'_, Game> {
+        self.games.iter()
+    }
+
+    /// Adds a game to the end of the collection.
+    pub fn push(&mut self, game: Game) {
+        self.games.push(game);
+    }
+
+    /// Returns the collection as an SGF file, with one game tree per game.
+    pub fn to_sgf(&self) -> String {
+        self.write_sgf(&SgfOptions::default())
+    }
+
+    /// Returns the collection as an SGF file, written with the given options.
+    pub fn write_sgf(&self, options: &SgfOptions) -> String {
+        self.games.iter().map(|game| game.write_sgf(options)).collect()
+    }
+}
+
+impl Index<usize> for Collection {
+    type Output = Game;
+
+    fn index(&self, index: usize) -> &Game {
+        &self.games[index]
+    }
+}
+
+impl From<Vec<Game>> for Collection {
+    fn from(games: Vec<Game>) -> Collection {
+        Collection { games }
+    }
+}
+
+impl IntoIterator for Collection {
+    type Item = Game;
+    type IntoIter = ::std::vec::IntoIter<Game>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.games.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Collection {
+    type Item = &'a Game;
+    type IntoIter = slice::Iter<'a, Game>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.games.iter()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Collection;
+    use board::Stone;
+    use game::Game;
+    use point::Point;
+
+    #[test]
+    fn parse_collection() {
+        let collection = Collection::parse("(;SZ[9]PB[Alice];B[ee])\n(;SZ[13]AB[aa](;W[bb])(;W[cc]))garbage");
+
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection[0].board().size, 9);
+        assert_eq!(collection[0].moves().len(), 1);
+        assert_eq!(collection[1].board().size, 13);
+        assert_eq!(collection[1].board()[Point::new(0, 0)], Stone::Black);
+        assert_eq!(collection[1].board()[Point::new(1, 1)], Stone::White);
+
+        // Reading a single game only reads the first one.
+        let text = "(;SZ[9];B[ee])(;SZ[13])";
+        assert_eq!(Game::parse_sgf(text), Collection::parse(text)[0]);
+    }
+
+    #[test]
+    fn empty_collection() {
+        assert!(Collection::parse("").is_empty());
+        assert_eq!(Collection::parse("").to_sgf(), "");
+    }
+
+    #[test]
+    fn write_collection() {
+        let mut collection = Collection::new();
+        let mut game = Game::new();
+        game.make_move(Stone::Black, (3, 3));
+
+        collection.push(game);
+        collection.push(Game::new());
+
+        let sgf = collection.to_sgf();
+
+        assert_eq!(sgf, "(;GM[1]FF[4]CA[UTF-8]SZ[19];B[dd])\n(;GM[1]FF[4]CA[UTF-8]SZ[19])\n");
+        assert_eq!(Collection::parse(&sgf), collection);
+        assert_eq!(collection.iter().count(), 2);
+    }
+}
@@ -0,0 +1,459 @@
+use std::error::Error;
+use std::fmt;
+
+use regex::Regex;
+
+use board::{Board, Stone};
+use game::Move;
+use markup::Markup;
+use point::Point;
+
+/// Symbols for stones with markup on them, as used by Sensei's Library.
+const MARKED_STONES: &[(char, Stone, Markup)] = &[
+    ('B', Stone::Black, Markup::Circle),
+    ('W', Stone::White, Markup::Circle),
+    ('#', Stone::Black, Markup::Square),
+    ('@', Stone::White, Markup::Square),
+    ('Y', Stone::Black, Markup::Triangle),
+    ('Q', Stone::White, Markup::Triangle),
+    ('Z', Stone::Black, Markup::Cross),
+    ('P', Stone::White, Markup::Cross),
+    ('C', Stone::Empty, Markup::Circle),
+    ('S', Stone::Empty, Markup::Square),
+    ('T', Stone::Empty, Markup::Triangle),
+    ('M', Stone::Empty, Markup::Cross),
+];
+
+/// The reason a diagram couldn't be parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagramError {
+    /// There are no lines starting with `$$`.
+    MissingHeader,
+    /// The first line isn't of the form `$$[B|W][c][size][m<number>] [title]`.
+    InvalidHeader,
+    /// The diagram contains a character that isn't a known symbol.
+    InvalidSymbol(char),
+    /// The diagram doesn't fit on a board of the given size.
+    TooLarge,
+}
+
+impl fmt::Display for DiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DiagramError::MissingHeader => write!(f, "diagram has no `$$` lines"),
+            DiagramError::InvalidHeader => write!(f, "diagram header is malformed"),
+            DiagramError::InvalidSymbol(c) => write!(f, "unknown diagram symbol `{}`", c),
+            DiagramError::TooLarge => write!(f, "diagram doesn't fit on the board"),
+        }
+    }
+}
+
+impl Error for DiagramError {}
+
+/// A position in the ASCII diagram format used by Sensei's Library (and GoDiagram):
+///
+/// ```text
+/// $$Wm5 Example
+/// $$ +-------------------+
+/// $$ | . . . . . . . . . |
+/// $$ | . . X 1 . . . . . |
+/// $$ | . . O 2 , . . a . |
+/// ```
+///
+/// Stones numbered `1` to `9` (and `0` for the tenth) are moves played from the position on
+/// `board`, alternating colors starting from `to_play`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagram {
+    /// The position before any of the numbered moves.
+    pub board: Board,
+    /// The numbered moves, in order.
+    pub moves: Vec<Move>,
+    /// Markup drawn on top of points.
+    pub markup: Vec<(Point, Markup)>,
+    /// The player who plays the move numbered `1` (or who is to play, if there are no moves).
+    pub to_play: Stone,
+    /// The move number of the stone numbered `1`.
+    pub first_number: usize,
+    /// Whether the diagram should be shown with coordinates.
+    pub coordinates: bool,
+    /// The title of the diagram.
+    pub title: Option<String>,
+    /// The top-left and bottom-right corners (inclusive) of the part of the board shown, or
+    /// `None` if the whole board is shown.
+    pub region: Option<(Point, Point)>,
+}
+
+impl Diagram {
+    /// Creates a diagram of the whole of `board`, with black to play.
+    pub fn new(board: Board) -> Diagram {
+        Diagram {
+            board,
+            moves: Vec::new(),
+            markup: Vec::new(),
+            to_play: Stone::Black,
+            first_number: 1,
+            coordinates: false,
+            title: None,
+            region: None,
+        }
+    }
+
+    /// Parses a diagram. Lines that don't start with `$$` are ignored.
+    ///
+    /// The board size is taken from the header if it is given, or from the width of the
+    /// diagram if all four edges of the board are shown, and is 19 otherwise. Diagrams of part
+    /// of the board are placed against the edges that they show.
+    pub fn parse(text: &str) -> Result<Diagram, DiagramError> {
+        let mut lines = text.lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with("$$"))
+            .map(|line| &line[2..]);
+
+        let header = lines.next().ok_or(DiagramError::MissingHeader)?;
+        let re = Regex::new(r"^([BW])?(c)?(\d+)?(?:m(\d+))?(?:\s+(.*))?$").expect("invalid regex");
+        let cap = re.captures(header).ok_or(DiagramError::InvalidHeader)?;
+
+        let to_play = if cap.get(1).map(|m| m.as_str()) == Some("W") { Stone::White } else { Stone::Black };
+        let number = |i: usize| cap.get(i).and_then(|m| m.as_str().parse::<usize>().ok());
+        let title = cap.get(5).map(|m| m.as_str().trim()).filter(|t| !t.is_empty());
+
+        let (mut top, mut bottom, mut left, mut right) = (false, false, false, false);
+        let mut rows: Vec<Vec<char>> = Vec::new();
+
+        for line in lines {
+            let symbols: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+
+            if symbols.is_empty() {
+                continue;
+            }
+
+            // A line of `-` (with `+` at the corners) is the top or bottom edge of the board.
+            if symbols.iter().all(|&c| c == '-' || c == '+') {
+                if rows.is_empty() { top = true; } else { bottom = true; }
+                continue;
+            }
+
+            let mut symbols = &symbols[..];
+
+            if symbols.first() == Some(&'|') {
+                left = true;
+                symbols = &symbols[1..];
+            }
+
+            if symbols.last() == Some(&'|') {
+                right = true;
+                symbols = &symbols[..symbols.len() - 1];
+            }
+
+            rows.push(symbols.to_vec());
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+
+        let size = match number(3) {
+            Some(0) => return Err(DiagramError::InvalidHeader),
+            Some(size) => size,
+            None if top && bottom && left && right => width.max(height),
+            None => Board::new().size,
+        };
+
+        if width > size || height > size {
+            return Err(DiagramError::TooLarge);
+        }
+
+        let x0 = if !left && right { size - width } else { 0 };
+        let y0 = if !top && bottom { size - height } else { 0 };
+
+        let mut diagram = Diagram {
+            to_play,
+            first_number: number(4).unwrap_or(1),
+            coordinates: cap.get(2).is_some(),
+            title: title.map(String::from),
+            region: if width == size && height == size {
+                None
+            } else {
+                Some((Point::new(x0, y0), Point::new(x0 + width.max(1) - 1, y0 + height.max(1) - 1)))
+            },
+            ..Diagram::new(Board::with_size(size))
+        };
+
+        let mut numbered = Vec::new();
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &symbol) in row.iter().enumerate() {
+                let point = Point::new(x0 + x, y0 + y);
+
+                match symbol {
+                    '.' | ',' | '_' => {},
+                    'X' => diagram.board[point] = Stone::Black,
+                    'O' => diagram.board[point] = Stone::White,
+                    '1'..='9' | '0' => numbered.push((symbol.to_digit(10).unwrap(), point)),
+                    'a'..='z' => diagram.markup.push((point, Markup::Label(symbol.to_string()))),
+                    _ => {
+                        let &(_, stone, ref markup) = MARKED_STONES.iter()
+                            .find(|&&(c, _, _)| c == symbol)
+                            .ok_or(DiagramError::InvalidSymbol(symbol))?;
+
+                        diagram.board[point] = stone;
+                        diagram.markup.push((point, markup.clone()));
+                    },
+                }
+            }
+        }
+
+        // `0` stands for the tenth move.
+        numbered.sort_by_key(|&(n, _)| if n == 0 { 10 } else { n });
+
+        diagram.moves = numbered.into_iter()
+            .map(|(n, point)| Move {
+                stone: if n % 2 == 1 { to_play } else { to_play.not() },
+                point,
+            })
+            .collect();
+
+        Ok(diagram)
+    }
+
+    /// Returns the symbol shown at `point`. Only the first ten moves can be numbered.
+    fn symbol(&self, point: Point) -> char {
+        if let Some(i) = self.moves.iter().take(10).position(|m| m.point == point) {
+            return std::char::from_digit((i as u32 + 1) % 10, 10).unwrap();
+        }
+
+        let stone = self.board[point];
+
+        for (_, markup) in self.markup.iter().filter(|&&(p, _)| p == point) {
+            if let Markup::Label(ref label) = *markup {
+                match label.chars().next() {
+                    Some(c @ 'a'..='z') if stone == Stone::Empty => return c,
+                    _ => continue,
+                }
+            }
+
+            if let Some(&(c, _, _)) = MARKED_STONES.iter().find(|&&(_, s, ref m)| s == stone && m == markup) {
+                return c;
+            }
+        }
+
+        match stone {
+            Stone::Black => 'X',
+            Stone::White => 'O',
+            Stone::Empty if self.board.star_point(point) => ',',
+            Stone::Empty => '.',
+        }
+    }
+}
+
+impl fmt::Display for Diagram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let last = self.board.size - 1;
+        let (min, max) = self.region.unwrap_or((Point::new(0, 0), Point::new(last, last)));
+        let (left, right) = (min.x == 0, max.x == last);
+
+        write!(f, "$${}", if self.to_play == Stone::White { "W" } else { "B" })?;
+
+        if self.coordinates {
+            write!(f, "c")?;
+        }
+
+        if self.board.size != Board::new().size {
+            write!(f, "{}", self.board.size)?;
+        }
+
+        if self.first_number != 1 {
+            write!(f, "m{}", self.first_number)?;
+        }
+
+        if let Some(ref title) = self.title {
+            write!(f, " {}", title)?;
+        }
+
+        let edge = format!("$$ {}{}{}",
+            if left { "+-" } else { "" },
+            "-".repeat(2 * (max.x - min.x) + 1),
+            if right { "-+" } else { "" });
+
+        if min.y == 0 {
+            write!(f, "\n{}", edge)?;
+        }
+
+        for y in min.y..=max.y {
+            let symbols: Vec<String> = (min.x..=max.x)
+                .map(|x| self.symbol(Point::new(x, y)).to_string())
+                .collect();
+
+            write!(f, "\n$$ {}{}{}",
+                if left { "| " } else { "" },
+                symbols.join(" "),
+                if right { " |" } else { "" })?;
+        }
+
+        if max.y == last {
+            write!(f, "\n{}", edge)?;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagram, DiagramError};
+    use board::{Board, Stone};
+    use game::Move;
+    use markup::Markup;
+    use point::Point;
+
+    #[test]
+    fn whole_board() {
+        let diagram = Diagram::parse("\
+            $$c5 Five by five\n\
+            $$ +-----------+\n\
+            $$ | . . . . . |\n\
+            $$ | . X O . . |\n\
+            $$ | . . . . . |\n\
+            $$ | . . . . . |\n\
+            $$ | . . . . X |\n\
+            $$ +-----------+").unwrap();
+
+        assert_eq!(diagram.board, Board::from_str("\
+            ..... \
+            .#O.. \
+            ..... \
+            ..... \
+            ....#"));
+        assert_eq!(diagram.title, Some("Five by five".to_string()));
+        assert_eq!(diagram.to_play, Stone::Black);
+        assert!(diagram.coordinates);
+        assert_eq!(diagram.region, None);
+    }
+
+    #[test]
+    fn size_from_edges() {
+        let diagram = Diagram::parse("\
+            $$\n\
+            $$ -------\n\
+            $$ | . . . |\n\
+            $$ | . X . |\n\
+            $$ | . . . |\n\
+            $$ -------").unwrap();
+
+        assert_eq!(diagram.board.size, 3);
+        assert_eq!(diagram.board[(1, 1)], Stone::Black);
+    }
+
+    #[test]
+    fn corner_fragment() {
+        let diagram = Diagram::parse("\
+            $$W\n\
+            $$ ----------+\n\
+            $$ . . . . . |\n\
+            $$ . . O . . |\n\
+            $$ . . . X . |").unwrap();
+
+        assert_eq!(diagram.board.size, 19);
+        assert_eq!(diagram.board[(16, 1)], Stone::White);
+        assert_eq!(diagram.board[(17, 2)], Stone::Black);
+        assert_eq!(diagram.to_play, Stone::White);
+        assert_eq!(diagram.region, Some((Point::new(14, 0), Point::new(18, 2))));
+    }
+
+    #[test]
+    fn numbered_moves() {
+        let diagram = Diagram::parse("\
+            $$Wm21\n\
+            $$ +-------+\n\
+            $$ | 3 . 0 |\n\
+            $$ | 1 X 2 |\n\
+            $$ | . . . |\n\
+            $$ +-------+").unwrap();
+
+        assert_eq!(diagram.first_number, 21);
+        assert_eq!(diagram.board[(0, 1)], Stone::Empty);
+        assert_eq!(diagram.moves, vec![
+            Move { stone: Stone::White, point: Point::new(0, 1) },
+            Move { stone: Stone::Black, point: Point::new(2, 1) },
+            Move { stone: Stone::White, point: Point::new(0, 0) },
+            Move { stone: Stone::Black, point: Point::new(2, 0) },
+        ]);
+    }
+
+    #[test]
+    fn markup() {
+        let diagram = Diagram::parse("\
+            $$\n\
+            $$ +-------+\n\
+            $$ | B W # |\n\
+            $$ | Q C T |\n\
+            $$ | a . b |\n\
+            $$ +-------+").unwrap();
+
+        assert_eq!(diagram.board, Board::from_str("#O# O.. ..."));
+        assert_eq!(diagram.markup, vec![
+            (Point::new(0, 0), Markup::Circle),
+            (Point::new(1, 0), Markup::Circle),
+            (Point::new(2, 0), Markup::Square),
+            (Point::new(0, 1), Markup::Triangle),
+            (Point::new(1, 1), Markup::Circle),
+            (Point::new(2, 1), Markup::Triangle),
+            (Point::new(0, 2), Markup::Label("a".to_string())),
+            (Point::new(2, 2), Markup::Label("b".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Diagram::parse("no diagram here"), Err(DiagramError::MissingHeader));
+        assert_eq!(Diagram::parse("$$Black"), Err(DiagramError::InvalidHeader));
+        assert_eq!(Diagram::parse("$$\n$$ . ? ."), Err(DiagramError::InvalidSymbol('?')));
+        assert_eq!(Diagram::parse("$$2\n$$ . . ."), Err(DiagramError::TooLarge));
+    }
+
+    #[test]
+    fn write() {
+        let mut diagram = Diagram::new(Board::from_str("\
+            .#... \
+            ..... \
+            ..... \
+            ..... \
+            ....O"));
+
+        diagram.title = Some("Example".to_string());
+        diagram.first_number = 7;
+        diagram.moves.push(Move { stone: Stone::Black, point: Point::new(2, 2) });
+        diagram.moves.push(Move { stone: Stone::White, point: Point::new(3, 2) });
+        diagram.markup.push((Point::new(1, 0), Markup::Triangle));
+        diagram.markup.push((Point::new(0, 4), Markup::Label("a".to_string())));
+
+        assert_eq!(diagram.to_string(), "\
+            $$B5m7 Example\n\
+            $$ +-----------+\n\
+            $$ | . Y . . . |\n\
+            $$ | . . . . . |\n\
+            $$ | . . 1 2 . |\n\
+            $$ | . . . . . |\n\
+            $$ | a . . . O |\n\
+            $$ +-----------+");
+
+        assert_eq!(Diagram::parse(&diagram.to_string()), Ok(diagram));
+    }
+
+    #[test]
+    fn round_trip_fragment() {
+        let text = "\
+            $$B\n\
+            $$ | . . . , .\n\
+            $$ | . . X . .\n\
+            $$ | . O . . .\n\
+            $$ | . . . . .\n\
+            $$ +----------";
+
+        let diagram = Diagram::parse(text).unwrap();
+
+        assert_eq!(diagram.region, Some((Point::new(0, 15), Point::new(4, 18))));
+        assert_eq!(diagram.board[(2, 16)], Stone::Black);
+        assert_eq!(diagram.to_string(), text);
+    }
+}
@@ -0,0 +1,189 @@
+use board::{Board, Stone};
+use point::Point;
+
+/// The kind of eye an empty point forms for a player (see `Board::eye`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Eye {
+    /// The point can't be filled by the opponent, and the stones around it can't be separated.
+    Real,
+    /// The point is surrounded, but the opponent controls enough of its diagonals to cut off
+    /// some of the stones around it, so the point must eventually be filled to connect them.
+    False,
+}
+
+impl Board {
+    /// Classifies the empty point at `point` as a real or false eye for `color`, or returns
+    /// `None` if it isn't an eye at all (its neighbours aren't all `color` stones).
+    ///
+    /// An eye is false when the opponent holds two of its diagonals, or one if it lies on the
+    /// edge or in the corner of the board, unless the stones around it already belong to a
+    /// single chain (so there's nothing for the opponent to cut).
+    pub fn eye<P: Into<Point>>(&self, point: P, color: Stone) -> Option<Eye> {
+        let point = point.into();
+
+        if color == Stone::Empty || self.get(point) != Some(Stone::Empty) {
+            return None;
+        }
+
+        let neighbours = self.neighbours(point);
+
+        if neighbours.iter().any(|&p| self[p] != color) {
+            return None;
+        }
+
+        let chain = self.chain_at(neighbours[0]);
+
+        if neighbours.iter().all(|p| chain.contains(p)) {
+            return Some(Eye::Real);
+        }
+
+        let diagonals = self.diagonals(point);
+        let opponent = diagonals.iter().filter(|&&p| self[p] == color.not()).count();
+
+        // A point away from the edge has four diagonals, and the opponent needs two of them.
+        let limit = if diagonals.len() == 4 { 2 } else { 1 };
+
+        if opponent >= limit { Some(Eye::False) } else { Some(Eye::Real) }
+    }
+
+    /// Returns whether the empty point at `point` is a real eye for `color`.
+    pub fn is_eye<P: Into<Point>>(&self, point: P, color: Stone) -> bool {
+        self.eye(point, color) == Some(Eye::Real)
+    }
+
+    /// Returns the positions diagonally adjacent to `point`.
+    pub fn diagonals<P: Into<Point>>(&self, point: P) -> Vec<Point> {
+        let Point { x, y } = point.into();
+        let mut positions = Vec::new();
+
+        if x > 0 && y > 0 { positions.push(Point::new(x - 1, y - 1)) }
+        if x < (self.size - 1) && y > 0 { positions.push(Point::new(x + 1, y - 1)) }
+        if x > 0 && y < (self.size - 1) { positions.push(Point::new(x - 1, y + 1)) }
+        if x < (self.size - 1) && y < (self.size - 1) { positions.push(Point::new(x + 1, y + 1)) }
+
+        positions
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Eye;
+    use board::{Board, Stone};
+
+    #[test]
+    fn diagonals() {
+        let board = Board::with_size(5);
+
+        assert_eq!(board.diagonals((2, 2)).len(), 4);
+        assert_eq!(board.diagonals((2, 0)).len(), 2);
+        assert_eq!(board.diagonals((0, 0)).len(), 1);
+        assert_eq!(board.diagonals((4, 4)), vec![(3, 3).into()]);
+    }
+
+    #[test]
+    fn real_eye_in_centre() {
+        let board = Board::from_str("\
+            ..... \
+            .O#.. \
+            .#.#. \
+            ..#.. \
+            .....");
+
+        // One diagonal isn't enough for the opponent away from the edge.
+        assert_eq!(board.eye((2, 2), Stone::Black), Some(Eye::Real));
+        assert!(board.is_eye((2, 2), Stone::Black));
+    }
+
+    #[test]
+    fn false_eye_in_centre() {
+        let board = Board::from_str("\
+            ..... \
+            .O#O. \
+            .#.#. \
+            ..#.. \
+            .....");
+
+        assert_eq!(board.eye((2, 2), Stone::Black), Some(Eye::False));
+        assert!(!board.is_eye((2, 2), Stone::Black));
+    }
+
+    #[test]
+    fn eyes_on_edge() {
+        let board = Board::from_str("\
+            .#.#. \
+            ..#O. \
+            ..... \
+            ..... \
+            .....");
+
+        assert_eq!(board.eye((2, 0), Stone::Black), Some(Eye::False));
+
+        let board = Board::from_str("\
+            .#.#. \
+            ..#.. \
+            ..... \
+            ..... \
+            .....");
+
+        assert_eq!(board.eye((2, 0), Stone::Black), Some(Eye::Real));
+    }
+
+    #[test]
+    fn eyes_in_corner() {
+        let board = Board::from_str("\
+            .#... \
+            #O... \
+            ..... \
+            ..... \
+            .....");
+
+        assert_eq!(board.eye((0, 0), Stone::Black), Some(Eye::False));
+
+        let board = Board::from_str("\
+            .#... \
+            ##... \
+            ..... \
+            ..... \
+            .....");
+
+        assert_eq!(board.eye((0, 0), Stone::Black), Some(Eye::Real));
+    }
+
+    #[test]
+    fn connected_stones_make_real_eyes() {
+        // White holds two diagonals, but the stones around the eye are already a single chain,
+        // so there's nothing to cut.
+        let board = Board::from_str("\
+            .#####. \
+            .#.#.#. \
+            .#O#O#. \
+            .##.##. \
+            ..###.. \
+            ....... \
+            .......");
+
+        assert_eq!(board.eye((3, 3), Stone::Black), Some(Eye::Real));
+    }
+
+    #[test]
+    fn not_eyes() {
+        let board = Board::from_str("\
+            .#O.. \
+            #.#.. \
+            .#... \
+            ..... \
+            ....#");
+
+        // Points with an opponent or empty neighbour, and occupied points, aren't eyes.
+        assert_eq!(board.eye((0, 0), Stone::White), None);
+        assert_eq!(board.eye((3, 1), Stone::Black), None);
+        assert_eq!(board.eye((2, 0), Stone::White), None);
+        assert_eq!(board.eye((1, 1), Stone::White), None);
+        assert_eq!(board.eye((1, 1), Stone::Empty), None);
+        assert_eq!(board.eye((9, 9), Stone::Black), None);
+
+        assert_eq!(board.eye((0, 0), Stone::Black), Some(Eye::Real));
+        assert_eq!(board.eye((1, 1), Stone::Black), Some(Eye::Real));
+    }
+}
@@ -1,24 +1,96 @@
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::collections::HashSet;
 use std::fmt;
 use std::fs::File;
 use std::io::Read;
+use std::mem;
 use std::path::Path;
+use std::time::Duration;
 
-use regex::Regex;
-
-use board::{Board, Stone};
+use board::{Board, IllegalMove, Stone};
+use charset;
+use clock::{Clock, TimeControl, TimeSettings, Timeout};
+use node::Node;
+use point::Point;
+use result::GameResult;
+use sgf::{self, SgfOptions};
+use text::BoardFormatter;
 
 #[derive(Clone, Debug, Default, PartialEq)]
-struct Player {
-    name: Option<String>,
-    rank: Option<String>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub(crate) struct Player {
+    pub(crate) name: Option<String>,
+    pub(crate) rank: Option<String>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq)]
+/// A stone played at a point, as recorded in the history of a `Game`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Move {
+    pub stone: Stone,
+    pub point: Point,
+}
+
+/// What was played at a node of a `Game`: either a stone, or a pass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Play {
+    /// A stone played at a point.
+    Move(Move),
+    /// A turn passed without playing a stone.
+    Pass(Stone),
+}
+
+impl Play {
+    /// Returns the color of the player who made the play.
+    pub fn stone(self) -> Stone {
+        match self {
+            Play::Move(m) => m.stone,
+            Play::Pass(stone) => stone,
+        }
+    }
+}
+
+/// A game of Go, tracking the players and the history of moves (for the ko rule).
+///
+/// With the `serde` feature enabled, a game is serialized as its initial position followed by
+/// its nodes, which are replayed (and checked for legality) on deserialization. Each node has
+/// its move (or pass) and setup stones, along with any comments, markup and times that aren't
+/// empty. The time settings of the clock are serialized, and the clock is resumed from the
+/// times recorded in the nodes:
+///
+/// ```json
+/// {
+///   "black": { "name": "Lee Sedol", "rank": "9p" },
+///   "white": { "name": "AlphaGo", "rank": null },
+///   "komi": 7.5,
+///   "result": { "resignation": "white" },
+///   "time_settings": null,
+///   "setup": { "size": 3, "rows": ["...", "...", "..."] },
+///   "nodes": [
+///     {},
+///     { "play": { "move": { "stone": "black", "point": { "x": 1, "y": 1 } } }, "comment": "Tengen" },
+///     { "play": { "pass": "white" } },
+///     { "setup": [[{ "x": 0, "y": 0 }, "white"]] }
+///   ]
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "GameRecord", into = "GameRecord"))]
 pub struct Game {
-    board: Board,
-    last_board: Option<Board>,
-    black: Player,
-    white: Player,
+    pub(crate) board: Board,
+    history: Vec<Board>,
+    moves: Vec<Move>,
+    nodes: Vec<Node>,
+    positions: Vec<Board>,
+    pub(crate) black: Player,
+    pub(crate) white: Player,
+    pub(crate) clock: Option<Clock>,
+    komi: f32,
+    result: Option<GameResult>,
 }
 
 impl Game {
@@ -28,6 +100,7 @@ impl Game {
     }
 
     /// Creates a new game from a string representation of the board state.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(board: &str) -> Game {
         Game {
             board: Board::from_str(board),
@@ -35,123 +108,665 @@ impl Game {
         }
     }
 
-    /// Creates a game from a given SGF file.
+    /// Returns the current state of the board.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Estimates which stones on the board are dead (see `Board::dead_stones`).
+    pub fn dead_stones(&self) -> HashSet<Point> {
+        self.board.dead_stones()
+    }
+
+    /// Returns the number of stones `stone` has captured so far.
+    pub fn captures(&self, stone: Stone) -> usize {
+        let count = |board: &Board| board.points().filter(|&p| board[p] == stone.not()).count();
+
+        // The position after each node is the one recorded before the next (or the current one).
+        self.nodes.iter().skip(1)
+            .zip(self.positions.iter().zip(self.positions.iter().skip(1).chain(Some(&self.board))))
+            .filter(|&(node, _)| node.played().is_some_and(|m| m.stone == stone))
+            .map(|(_, (before, after))| count(before).saturating_sub(count(after)))
+            .sum()
+    }
+
+    /// Estimates the score of the game as it stands, as the number of points black is ahead by
+    /// (negative if white is ahead), using territory scoring with komi and captures.
+    ///
+    /// Empty points enclosed by one color's living stones are that color's territory, while
+    /// those in open areas count towards the player who owns them (see `Board::ownership`) in
+    /// proportion to how certainly they are owned. Points next to chains in seki aren't anyone's
+    /// territory, and dead stones count as both territory and captures for their opponent. This
+    /// is cheap enough to run after every move.
+    pub fn estimate_score(&self) -> f32 {
+        let board = &self.board;
+        let seki = board.seki();
+        let dead = board.dead_stones_with_seki(&seki);
+        let mut ownership = board.ownership_with_dead(&dead);
+        let open = |p: Point| board[p] == Stone::Empty || dead.contains(&p);
+        let mut seen = HashSet::new();
+
+        for point in board.points() {
+            if !open(point) || seen.contains(&point) {
+                continue;
+            }
+
+            let region = board.region(point, open);
+            let border = board.border(&region);
+            seen.extend(region.iter().cloned());
+
+            let enclosed_by = |stone| !border.is_empty() && border.iter().all(|&p| board[p] == stone);
+            let owner = match () {
+                _ if border.iter().any(|p| seki.contains(p)) => 0.0,
+                _ if enclosed_by(Stone::Black) => 1.0,
+                _ if enclosed_by(Stone::White) => -1.0,
+                _ => continue,
+            };
+
+            for p in region {
+                ownership[p.y * board.size + p.x] = owner;
+            }
+        }
+
+        let territory: f32 = board.points().zip(ownership)
+            .map(|(point, owner)| match board[point] {
+                Stone::Black if dead.contains(&point) => -2.0,
+                Stone::White if dead.contains(&point) => 2.0,
+                Stone::Empty => owner,
+                _ => 0.0,
+            })
+            .sum();
+
+        territory + self.captures(Stone::Black) as f32 - self.captures(Stone::White) as f32 - self.komi
+    }
+
+    /// Returns the positions before each move that has been played so far, in order.
+    pub fn history(&self) -> &[Board] {
+        &self.history
+    }
+
+    /// Returns the moves that have been played so far, in order. Passes and illegal moves read
+    /// from a game record aren't included (see `Game::nodes`).
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Returns the nodes of the game record, starting from the root node (the initial position).
+    /// Each move is played in a node of its own.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns the node of the current position.
+    pub fn node(&self) -> &Node {
+        self.nodes.last().expect("game has no root node")
+    }
+
+    /// Returns the node of the current position, to add comments or markup to it.
+    pub fn node_mut(&mut self) -> &mut Node {
+        self.nodes.last_mut().expect("game has no root node")
+    }
+
+    /// Returns a cursor at the root node, for stepping through the positions of the game.
+    pub fn cursor(&self) -> Cursor<'_> {
+        Cursor { game: self, index: 0, moves: 0 }
+    }
+
+    /// Returns the most recently played move, if any.
+    pub fn last_move(&self) -> Option<Move> {
+        self.moves.last().cloned()
+    }
+
+    /// Returns the result of the game, if it has finished.
+    pub fn result(&self) -> Option<GameResult> {
+        self.result
+    }
+
+    /// Sets (or clears) the result of the game.
+    pub fn set_result(&mut self, result: Option<GameResult>) {
+        self.result = result;
+    }
+
+    /// Returns the number of points given to white for playing second.
+    pub fn komi(&self) -> f32 {
+        self.komi
+    }
+
+    /// Sets the number of points given to white for playing second.
+    pub fn set_komi(&mut self, komi: f32) {
+        self.komi = komi;
+    }
+
+    /// Returns the game clock, if the game is played with time limits.
+    pub fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    /// Starts a clock with the given time limits, replacing any previous one.
+    pub fn set_clock(&mut self, settings: TimeSettings) {
+        self.clock = Some(Clock::new(settings));
+    }
+
+    /// Records that `stone` took `elapsed` to play the last move, storing the time they have
+    /// left in its node. If they ran out of time, they lose the game.
+    pub fn record_time(&mut self, stone: Stone, elapsed: Duration) -> Result<(), Timeout> {
+        let clock = match self.clock {
+            Some(ref mut clock) => clock,
+            None => return Ok(()),
+        };
+
+        let recorded = clock.record_move(stone, elapsed);
+        let time_left = clock.time_left(stone);
+
+        match recorded {
+            Ok(()) if stone == Stone::Black => self.node_mut().black_time = Some(time_left),
+            Ok(()) => self.node_mut().white_time = Some(time_left),
+            Err(Timeout(stone)) => self.result = Some(GameResult::Time(stone.not())),
+        }
+
+        recorded
+    }
+
+    /// Checks whether `stone` has run out of time after thinking for `elapsed` without playing
+    /// a move, in which case they lose the game.
+    pub fn check_time(&mut self, stone: Stone, elapsed: Duration) -> Result<(), Timeout> {
+        let checked = match self.clock {
+            Some(ref mut clock) => clock.check(stone, elapsed).map(|_| ()),
+            None => Ok(()),
+        };
+
+        if let Err(Timeout(stone)) = checked {
+            self.result = Some(GameResult::Time(stone.not()));
+        }
+
+        checked
+    }
+
+    /// Renders the players and the current board state using the given formatter.
+    pub fn format(&self, formatter: &BoardFormatter) -> String {
+        let unknown = String::from("<unknown>");
+
+        let black_player = self.black.name.as_ref().unwrap_or(&unknown);
+        let white_player = self.white.name.as_ref().unwrap_or(&unknown);
+
+        format!("Black Player: {}\nWhite Player: {}\n{}", black_player, white_player,
+            formatter.format(&self.board))
+    }
+
+    /// Creates a game from a given SGF file, decoded using the character set given by its `CA`
+    /// property.
     pub fn from_sgf<P: AsRef<Path>>(path: P) -> Game {
         let mut f = File::open(path).expect("invalid path");
-        let mut contents = String::new();
-        f.read_to_string(&mut contents).unwrap();
+        let mut bytes = Vec::new();
+        f.read_to_end(&mut bytes).unwrap();
+
+        let declared = charset::sgf_charset(&bytes);
+        Game::parse_sgf(&charset::decode(&bytes, declared.as_deref()))
+    }
+
+    /// Creates a game from the contents of an SGF file, following the main line of the first
+    /// game tree (other variations are ignored). Use `Collection::parse` to read every game in a
+    /// file.
+    pub fn parse_sgf(contents: &str) -> Game {
+        sgf::parse(contents).first().map(Game::from_tree).unwrap_or_default()
+    }
 
+    /// Creates a game from the main line of an SGF game tree.
+    pub(crate) fn from_tree(tree: &sgf::Tree) -> Game {
         let mut game = Game::new();
+        let mut main_time = None;
+        let mut control = None;
 
-        // Enum containing various SGF properties
-        enum SGF {
-            AddStone(Stone),
-            Move(Stone),
-            PlayerName(Stone),
-            PlayerRank(Stone),
-            Unknown,
-        }
+        for (i, properties) in tree.main_line().into_iter().enumerate() {
+            let mut play = None;
+            let mut setup = Vec::new();
 
-        // TODO: Write an actual SGF parser instead of naively using regexes
-        let re = Regex::new(r"(\w{1,2})\[(.+?)\]").expect("invalid regex");
-
-        // Parse captured regex matches into SGF properties
-        let properties = re.captures_iter(&contents).map(|cap| {
-            let property = match &cap[1] {
-                "B"  => SGF::Move(Stone::Black),
-                "W"  => SGF::Move(Stone::White),
-                "AB" => SGF::AddStone(Stone::Black),
-                "AW" => SGF::AddStone(Stone::White),
-                "PB" => SGF::PlayerName(Stone::Black),
-                "PW" => SGF::PlayerName(Stone::White),
-                "BR" => SGF::PlayerRank(Stone::Black),
-                "WR" => SGF::PlayerRank(Stone::White),
-                _    => SGF::Unknown,
-            };
+            for property in properties {
+                let value = property.values.first().cloned().unwrap_or_default();
 
-            (property, cap[2].to_string())
-        });
+                match property.ident.as_str() {
+                    "B" => play = Some((Stone::Black, value)),
+                    "W" => play = Some((Stone::White, value)),
+                    "AB" | "AW" | "AE" => {
+                        let stone = match property.ident.as_str() {
+                            "AB" => Stone::Black,
+                            "AW" => Stone::White,
+                            _ => Stone::Empty,
+                        };
 
-        for (prop, val) in properties {
-            match prop {
-                SGF::Move(stone) => {
-                    // Use `Game::make_move` to take into account captures.
-                    let (x, y) = Self::alpha_to_xy(&val);
-                    game.make_move(stone, x, y);
-                },
-                SGF::AddStone(stone) => {
-                    // Manually assign stone to position.
-                    game.board[Self::alpha_to_xy(&val)] = stone;
-                },
-                SGF::PlayerName(stone) => {
-                    if stone == Stone::Black {
-                        game.black.name = Some(val);
-                    } else {
-                        game.white.name = Some(val);
-                    }
+                        setup.push((stone, &property.values));
+                    },
+                    "PB" => game.black.name = Some(value),
+                    "PW" => game.white.name = Some(value),
+                    "BR" => game.black.rank = Some(value),
+                    "WR" => game.white.rank = Some(value),
+                    "KM" => game.komi = value.trim().parse().unwrap_or_default(),
+                    "RE" => game.result = GameResult::from_sgf(&value),
+                    "TM" => main_time = value.trim().parse().ok().and_then(|t| Duration::try_from_secs_f64(t).ok()),
+                    "OT" => control = TimeControl::from_sgf(&value),
+                    // The board size can only be set in the root node, before any moves.
+                    "SZ" if i == 0 => {
+                        if let Ok(size) = value.trim().parse() {
+                            game.board = Board::with_size(size);
+                        }
+                    },
+                    _ => {},
+                }
+            }
+
+            // Passes are either empty or off-board coordinates (such as `tt`).
+            match play {
+                Some((stone, value)) => {
+                    let point = Point::from_sgf(&value, game.board.size);
+                    game.replay(stone, point);
                 },
-                SGF::PlayerRank(stone) => {
-                    if stone == Stone::Black {
-                        game.black.rank = Some(val);
-                    } else {
-                        game.white.rank = Some(val);
-                    }
+                None if i > 0 => game.push_node(Node::default()),
+                None => {},
+            }
+
+            // Points are only parsed once `SZ` has been read, which may come after them.
+            let size = game.board.size;
+            let setup: Vec<(Point, Stone)> = setup.into_iter()
+                .flat_map(|(stone, values)| sgf::parse_points(values, size).into_iter().map(move |p| (p, stone)))
+                .collect();
+
+            // Setup stones in the root node are part of the initial position, while those in
+            // other nodes are recorded so that they can be undone.
+            if i == 0 {
+                for (point, stone) in setup {
+                    game.board[point] = stone;
+                }
+            } else {
+                for (point, stone) in setup {
+                    game.board[point] = stone;
+                    game.node_mut().add_setup(point, stone);
                 }
-                _ => {},
             }
+
+            for property in properties {
+                game.node_mut().read_property(property, size);
+            }
+        }
+
+        if main_time.is_some() || control.is_some() {
+            game.resume_clock(TimeSettings {
+                main_time: main_time.unwrap_or_default(),
+                control: control.unwrap_or(TimeControl::Absolute),
+            });
         }
 
         game
     }
 
-    /// Places `stone` at `(x, y)`, returning true if it was successful (respecting the ko rule).
-    pub fn make_move(&mut self, stone: Stone, x: usize, y: usize) -> bool {
+    /// Returns the game as an SGF file, with the initial position given as setup stones.
+    pub fn to_sgf(&self) -> String {
+        self.write_sgf(&SgfOptions::default())
+    }
+
+    /// Returns the game as an SGF file, written with the given options.
+    pub fn write_sgf(&self, options: &SgfOptions) -> String {
+        let setup = self.positions.first().unwrap_or(&self.board);
+        let mut sgf = format!("(;GM[1]FF[4]CA[UTF-8]SZ[{}]", setup.size);
+
+        let properties = [
+            ("PB", &self.black.name), ("BR", &self.black.rank),
+            ("PW", &self.white.name), ("WR", &self.white.rank),
+        ];
+
+        for &(property, value) in &properties {
+            if let Some(ref value) = *value {
+                sgf::write_property(&mut sgf, property, &[value]);
+            }
+        }
+
+        if self.komi != 0.0 {
+            sgf::write_property(&mut sgf, "KM", &[self.komi.to_string()]);
+        }
+
+        if let Some(result) = self.result {
+            sgf::write_property(&mut sgf, "RE", &[result.to_string()]);
+        }
+
+        if let Some(ref clock) = self.clock {
+            let settings = clock.settings();
+            sgf::write_property(&mut sgf, "TM", &[settings.main_time.as_secs_f64().to_string()]);
+
+            if let Some(overtime) = settings.control.to_sgf() {
+                sgf::write_property(&mut sgf, "OT", &[overtime]);
+            }
+        }
+
+        for &(property, stone) in &[("AB", Stone::Black), ("AW", Stone::White)] {
+            let points: Vec<Point> = setup.points().filter(|&p| setup[p] == stone).collect();
+            sgf::write_property(&mut sgf, property, &sgf::format_points(&points, options));
+        }
+
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                sgf.push(';');
+            }
+
+            node.write_sgf(&mut sgf, options);
+        }
+
+        sgf.push_str(")\n");
+        sgf
+    }
+
+    /// Checks whether placing `stone` at `point` is a valid play, returning the reason if it
+    /// isn't. Unlike `Board::check_move`, this takes the ko and positional superko rules into
+    /// account.
+    pub fn check_move<P: Into<Point>>(&self, stone: Stone, point: P) -> Result<(), IllegalMove> {
+        self.next_board(stone, point).map(|_| ())
+    }
+
+    /// Returns an iterator over every point where `stone` can legally be played.
+    pub fn legal_moves<'a>(&'a self, stone: Stone) -> impl Iterator<Item = Point> + 'a {
+        self.board.legal_moves(stone).filter(move |&point| self.check_move(stone, point).is_ok())
+    }
+
+    /// Places `stone` at `point`, returning true if it was successful (respecting the ko rule).
+    pub fn make_move<P: Into<Point>>(&mut self, stone: Stone, point: P) -> bool {
+        self.try_move(stone, point).is_ok()
+    }
+
+    /// Places `stone` at `point`, returning the reason the move is illegal if it wasn't
+    /// successful (respecting the ko rule). The game is left untouched if the move is illegal.
+    pub fn try_move<P: Into<Point>>(&mut self, stone: Stone, point: P) -> Result<(), IllegalMove> {
+        let point = point.into();
+        let next_board = self.next_board(stone, point)?;
+        let board = mem::replace(&mut self.board, next_board);
+        self.history.push(board.clone());
+        self.positions.push(board);
+        self.moves.push(Move { stone, point });
+        self.nodes.push(Node::with_play(Play::Move(Move { stone, point })));
+
+        Ok(())
+    }
+
+    /// Records that `stone` passed, in a node of its own (which can be undone).
+    pub fn pass(&mut self, stone: Stone) {
+        self.push_node(Node::with_play(Play::Pass(stone)));
+    }
+
+    /// Plays a move read from a game record, where `None` is a pass. Illegal moves are kept in
+    /// a node of their own without changing the board, so that the record isn't altered.
+    pub(crate) fn replay(&mut self, stone: Stone, point: Option<Point>) {
+        match point {
+            Some(point) => {
+                if self.try_move(stone, point).is_err() {
+                    self.push_node(Node::with_illegal_move(Move { stone, point }));
+                }
+            },
+            None => self.pass(stone),
+        }
+    }
+
+    /// Places or removes (with `Stone::Empty`) stones without playing a move, as with the SGF
+    /// `AB`, `AW` and `AE` properties. Before any moves have been played this changes the
+    /// initial position, and otherwise the changes are made in a new node, which can be undone.
+    /// The game is left untouched if any of the points lie outside of the board.
+    pub fn setup(&mut self, changes: &[(Point, Stone)]) -> Result<(), IllegalMove> {
+        if changes.iter().any(|&(p, _)| !p.in_bounds(self.board.size)) {
+            return Err(IllegalMove::OutOfBounds);
+        }
+
+        let root = self.nodes.len() == 1;
+
+        if !root {
+            self.push_node(Node::default());
+        }
+
+        for &(point, stone) in changes {
+            self.board[point] = stone;
+
+            if !root {
+                self.node_mut().add_setup(point, stone);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the last node (a move, pass or setup changes), restoring the position before it.
+    /// Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let previous = match self.positions.pop() {
+            Some(previous) => previous,
+            None => return false,
+        };
+
+        let node = self.nodes.pop().expect("game has no root node");
+
+        if node.played().is_some() {
+            self.history.pop();
+            self.moves.pop();
+        }
+
+        self.board = previous;
+        true
+    }
+
+    /// Starts a clock with the given time limits, resumed from the last time recorded for each
+    /// player in the nodes of the game.
+    fn resume_clock(&mut self, settings: TimeSettings) {
+        let mut clock = Clock::new(settings);
+
+        for node in &self.nodes {
+            if let Some(time_left) = node.black_time {
+                clock.set_time_left(Stone::Black, time_left);
+            }
+
+            if let Some(time_left) = node.white_time {
+                clock.set_time_left(Stone::White, time_left);
+            }
+        }
+
+        self.clock = Some(clock);
+    }
+
+    /// Adds a node without playing a stone, recording the current position so that it can be
+    /// undone.
+    fn push_node(&mut self, node: Node) {
+        self.positions.push(self.board.clone());
+        self.nodes.push(node);
+    }
+
+    /// Returns the board that results from placing `stone` at `point`.
+    fn next_board<P: Into<Point>>(&self, stone: Stone, point: P) -> Result<Board, IllegalMove> {
+        let point = point.into();
+        self.board.check_move(stone, point)?;
+
         let mut next_board = self.board.clone();
+        next_board.make_move(stone, point);
+
+        // Retaking a ko is the special case of repeating the position from one move ago.
+        if self.history.last() == Some(&next_board) {
+            return Err(IllegalMove::Ko);
+        } else if self.history.contains(&next_board) {
+            return Err(IllegalMove::Superko);
+        }
+
+        Ok(next_board)
+    }
+}
+
+impl Default for Game {
+    fn default() -> Game {
+        Game {
+            board: Board::default(),
+            history: Vec::new(),
+            moves: Vec::new(),
+            nodes: vec![Node::default()],
+            positions: Vec::new(),
+            black: Player::default(),
+            white: Player::default(),
+            clock: None,
+            komi: 0.0,
+            result: None,
+        }
+    }
+}
+
+/// A position in a `Game`, which can be moved forwards and backwards through the game record.
+#[derive(Clone, Copy, Debug)]
+pub struct Cursor<'a> {
+    game: &'a Game,
+    index: usize,
+    moves: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Returns the node at the cursor.
+    pub fn node(&self) -> &'a Node {
+        &self.game.nodes[self.index]
+    }
 
-        if !next_board.make_move(stone, x, y) {
+    /// Returns the board position at the cursor.
+    pub fn board(&self) -> &'a Board {
+        self.game.positions.get(self.index).unwrap_or(&self.game.board)
+    }
+
+    /// Returns the number of moves that have been played to reach the cursor.
+    pub fn move_number(&self) -> usize {
+        self.moves
+    }
+
+    /// Moves to the next node, returning false if the cursor is already at the last node.
+    pub fn forward(&mut self) -> bool {
+        if self.index + 1 >= self.game.nodes.len() {
             return false;
         }
 
-        if let Some(ref b) = self.last_board {
-            if *b == next_board {
-                return false;
-            }
+        self.index += 1;
+
+        if self.node().played().is_some() {
+            self.moves += 1;
+        }
+
+        true
+    }
+
+    /// Moves to the previous node, returning false if the cursor is already at the root node.
+    pub fn back(&mut self) -> bool {
+        if self.index == 0 {
+            return false;
         }
 
-        self.last_board = Some(self.board.clone());
-        self.board = next_board;
+        if self.node().played().is_some() {
+            self.moves -= 1;
+        }
 
+        self.index -= 1;
         true
     }
+}
 
-    /// Maps "alphabetical coordinates" to `(x, y)` coordinates.
-    /// Ex. "ab" => (0, 1); "zz" => (25, 25)
-    fn alpha_to_xy(alpha: &str) -> (usize, usize) {
-        let mut chars = alpha.chars();
-        let x = chars.next().expect("expected 2 characters");
-        let y = chars.next().expect("expected 2 characters");
+/// Serialized form of a `Game` (see the `Game` documentation for the schema).
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct GameRecord {
+    black: Player,
+    white: Player,
+    #[serde(default)]
+    komi: f32,
+    #[serde(default)]
+    result: Option<GameResult>,
+    #[serde(default)]
+    time_settings: Option<TimeSettings>,
+    setup: Board,
+    nodes: Vec<Node>,
+}
 
-        (x as usize - b'a' as usize, y as usize - b'a' as usize)
+#[cfg(feature = "serde")]
+impl From<Game> for GameRecord {
+    fn from(game: Game) -> GameRecord {
+        let setup = game.positions.first().cloned().unwrap_or(game.board);
+
+        GameRecord {
+            black: game.black,
+            white: game.white,
+            komi: game.komi,
+            result: game.result,
+            time_settings: game.clock.map(|clock| *clock.settings()),
+            setup,
+            nodes: game.nodes,
+        }
     }
 }
 
-impl fmt::Display for Game {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let unknown = String::from("<unknown>");
+#[cfg(feature = "serde")]
+impl TryFrom<GameRecord> for Game {
+    type Error = String;
 
-        let black_player = self.black.name.as_ref().unwrap_or(&unknown);
-        let white_player = self.white.name.as_ref().unwrap_or(&unknown);
+    fn try_from(record: GameRecord) -> Result<Game, String> {
+        let mut nodes = record.nodes.into_iter();
+        let root = nodes.next().unwrap_or_default();
+
+        if root.play().is_some() || !root.setup().is_empty() {
+            return Err("the root node can't have a move or setup".to_string());
+        }
+
+        let mut game = Game {
+            board: record.setup,
+            nodes: vec![root],
+            black: record.black,
+            white: record.white,
+            komi: record.komi,
+            result: record.result,
+            ..Default::default()
+        };
 
-        writeln!(f, "Black Player: {}", black_player)?;
-        writeln!(f, "White Player: {}", white_player)?;
-        write!(f, "{}", self.board)
+        // Each node is replayed, and then replaced by its full contents.
+        for (i, node) in nodes.enumerate() {
+            match node.played() {
+                Some(m) => game.try_move(m.stone, m.point)
+                    .map_err(|e| format!("move at {} in node {} is illegal: {}", m.point, i + 1, e))?,
+                None => game.push_node(Node::default()),
+            }
+
+            for &(point, stone) in node.setup() {
+                if !point.in_bounds(game.board.size) {
+                    return Err(format!("setup at {} in node {} is outside of the board", point, i + 1));
+                }
+
+                game.board[point] = stone;
+            }
+
+            *game.node_mut() = node;
+        }
+
+        if let Some(settings) = record.time_settings {
+            game.resume_clock(settings);
+        }
+
+        Ok(game)
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(&BoardFormatter::new()))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Game;
-    use board::{Board, Stone};
+    use std::time::Duration;
+
+    use super::{Game, Move, Play};
+    use board::{Board, IllegalMove, Stone};
+    use clock::{TimeControl, TimeLeft, TimeSettings, Timeout};
+    use markup::Markup;
+    use node::{Annotation, Emphasis};
+    use point::Point;
+    use result::GameResult;
+    use sgf::SgfOptions;
+    use text::BoardFormatter;
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
 
     #[test]
     fn new_game() {
@@ -172,7 +787,7 @@ mod tests {
             .#. \
             ...");
 
-        assert!(game.make_move(Stone::Black, 1, 1));
+        assert!(game.make_move(Stone::Black, (1, 1)));
         assert_eq!(game.board, expected.board);
     }
 
@@ -193,11 +808,13 @@ mod tests {
             .....");
 
         // Black capture is a valid play.
-        assert!(game.make_move(Stone::Black, 2, 1));
+        assert!(game.make_move(Stone::Black, (2, 1)));
         assert_eq!(game.board, expected.board);
 
         // White cannot capture due to the ko rule.
-        assert!(!game.make_move(Stone::White, 1, 1));
+        assert_eq!(game.check_move(Stone::White, (1, 1)), Err(IllegalMove::Ko));
+        assert_eq!(game.try_move(Stone::White, (1, 1)), Err(IllegalMove::Ko));
+        assert!(!game.make_move(Stone::White, (1, 1)));
         assert_eq!(game.board, expected.board);
     }
 
@@ -210,7 +827,7 @@ mod tests {
             #.#O. \
             ##OO.");
 
-        assert!(game.make_move(Stone::White, 1, 3));
+        assert!(game.make_move(Stone::White, (1, 3)));
         assert_eq!(game.board, Board::from_str("\
             #.#OO \
             .##O. \
@@ -219,8 +836,8 @@ mod tests {
             ##OO."));
 
         // Black cannot recapture due to the ko rule, so they play elsewhere instead.
-        assert!(!game.make_move(Stone::Black, 2, 3));
-        assert!(game.make_move(Stone::Black, 4, 2));
+        assert!(!game.make_move(Stone::Black, (2, 3)));
+        assert!(game.make_move(Stone::Black, (4, 2)));
         assert_eq!(game.board, Board::from_str("\
             #.#OO \
             .##O. \
@@ -228,7 +845,7 @@ mod tests {
             #O.O. \
             ##OO."));
 
-        assert!(game.make_move(Stone::White, 4, 3));
+        assert!(game.make_move(Stone::White, (4, 3)));
         assert_eq!(game.board, Board::from_str("\
             #.#OO \
             .##O. \
@@ -237,7 +854,7 @@ mod tests {
             ##OO."));
 
         // Black can capture at the location previously prevented by the ko rule.
-        assert!(game.make_move(Stone::Black, 2, 3));
+        assert!(game.make_move(Stone::Black, (2, 3)));
         assert_eq!(game.board, Board::from_str("\
             #.#OO \
             .##O. \
@@ -245,7 +862,7 @@ mod tests {
             #.#OO \
             ##OO."));
 
-        assert!(game.make_move(Stone::White, 4, 1));
+        assert!(game.make_move(Stone::White, (4, 1)));
         assert_eq!(game.board, Board::from_str("\
             #.#OO \
             .##OO \
@@ -253,7 +870,7 @@ mod tests {
             #.#OO \
             ##OO."));
 
-        assert!(game.make_move(Stone::Black, 1, 3));
+        assert!(game.make_move(Stone::Black, (1, 3)));
         assert_eq!(game.board, Board::from_str("\
             #.#OO \
             .##OO \
@@ -261,4 +878,454 @@ mod tests {
             ###OO \
             ##OO."));
     }
+
+    #[test]
+    fn superko_rule() {
+        // Three kos along the top edge, with the third one already taken by black.
+        let mut game = Game::from_str("\
+            #O.O#O.O#.#O \
+            .#O..#O..#O. \
+            .#O..#O..#O. \
+            ............ \
+            ............ \
+            ............ \
+            ............ \
+            ............ \
+            ............ \
+            ............ \
+            ............ \
+            ............");
+        let start = game.board.clone();
+
+        assert!(game.make_move(Stone::Black, (2, 0)));
+        assert!(game.make_move(Stone::White, (9, 0)));
+        assert!(game.make_move(Stone::Black, (6, 0)));
+        assert!(game.make_move(Stone::White, (1, 0)));
+        assert!(game.make_move(Stone::Black, (10, 0)));
+
+        // Retaking the second ko isn't an immediate recapture, but would recreate the position
+        // from the start of the cycle.
+        assert_eq!(game.check_move(Stone::White, (5, 0)), Err(IllegalMove::Superko));
+        assert!(!game.make_move(Stone::White, (5, 0)));
+        assert_ne!(game.board, start);
+    }
+
+    #[test]
+    fn check_move_reasons() {
+        let game = Game::from_str("\
+            .#. \
+            #.# \
+            ...");
+
+        assert_eq!(game.check_move(Stone::White, (2, 2)), Ok(()));
+        assert_eq!(game.check_move(Stone::White, (3, 3)), Err(IllegalMove::OutOfBounds));
+        assert_eq!(game.check_move(Stone::White, (1, 0)), Err(IllegalMove::Occupied));
+        assert_eq!(game.check_move(Stone::White, (0, 0)), Err(IllegalMove::Suicide));
+    }
+
+    #[test]
+    fn out_of_bounds_move() {
+        let mut game = Game::from_str("\
+            ... \
+            ... \
+            ...");
+
+        let expected = game.board.clone();
+
+        assert_eq!(game.try_move(Stone::Black, (3, 0)), Err(IllegalMove::OutOfBounds));
+        assert_eq!(game.try_move(Stone::Black, (usize::MAX, 1)), Err(IllegalMove::OutOfBounds));
+        assert_eq!(game.board, expected);
+        assert!(game.history.is_empty());
+    }
+
+    #[test]
+    fn sgf_comments_and_markup() {
+        let game = Game::parse_sgf("(;SZ[9]C[Game comment]\
+            ;B[cc]C[Black's move]TR[dd]BM[1]\
+            ;C[A node without a move]LB[ee:a]\
+            ;W[gg]N[Corner]\
+            (;B[gc]TE[2])(;B[cg]C[Variation]))");
+
+        assert_eq!(game.nodes().len(), 5);
+        assert_eq!(game.moves().len(), 3);
+        assert_eq!(game.node().annotations, vec![Annotation::Tesuji(Emphasis::Strong)]);
+
+        let mut cursor = game.cursor();
+        assert_eq!(cursor.node().comment, Some("Game comment".to_string()));
+        assert!(!cursor.back());
+
+        assert!(cursor.forward());
+        assert_eq!(cursor.node().comment, Some("Black's move".to_string()));
+        assert_eq!(cursor.node().markup, vec![(Point::new(3, 3), Markup::Triangle)]);
+        assert_eq!(cursor.node().play(), Some(Play::Move(Move { stone: Stone::Black, point: Point::new(2, 2) })));
+        assert_eq!(cursor.board()[(2, 2)], Stone::Black);
+        assert_eq!(cursor.move_number(), 1);
+
+        assert!(cursor.forward());
+        assert_eq!(cursor.node().play(), None);
+        assert_eq!(cursor.node().markup, vec![(Point::new(4, 4), Markup::Label("a".to_string()))]);
+        assert_eq!(cursor.move_number(), 1);
+
+        assert!(cursor.forward());
+        assert_eq!(cursor.node().name, Some("Corner".to_string()));
+        assert_eq!(cursor.board()[(6, 6)], Stone::White);
+
+        assert!(cursor.forward());
+        assert!(!cursor.forward());
+        assert_eq!(cursor.board(), game.board());
+
+        assert!(cursor.back() && cursor.back() && cursor.back());
+        assert_eq!(cursor.board()[(2, 2)], Stone::Black);
+        assert_eq!(cursor.board()[(6, 6)], Stone::Empty);
+    }
+
+    #[test]
+    fn write_sgf_nodes() {
+        let mut game = Game::from_str("\
+            ... \
+            ... \
+            ...");
+
+        game.node_mut().comment = Some("Start".to_string());
+        game.make_move(Stone::Black, (1, 1));
+        game.node_mut().markup.push((Point::new(0, 0), Markup::Square));
+        game.node_mut().annotations.push(Annotation::Value(0.5));
+
+        let sgf = game.to_sgf();
+        assert_eq!(sgf, "(;GM[1]FF[4]CA[UTF-8]SZ[3]C[Start];B[bb]V[0.5]SQ[aa])\n");
+        assert_eq!(Game::parse_sgf(&sgf), game);
+    }
+
+    #[test]
+    fn sgf_setup_nodes() {
+        let mut game = Game::parse_sgf("(;SZ[5]AB[aa][bb]AW[cc]\
+            ;B[dd]\
+            ;AW[ee]AE[aa][dd]C[Edited]\
+            ;W[ba])");
+
+        assert!(game.nodes()[0].setup().is_empty());
+        assert_eq!(game.nodes()[2].setup(), &[
+            (Point::new(4, 4), Stone::White),
+            (Point::new(0, 0), Stone::Empty),
+            (Point::new(3, 3), Stone::Empty),
+        ][..]);
+        assert_eq!(game.board(), &Board::from_str("\
+            .O... \
+            .#... \
+            ..O.. \
+            ..... \
+            ....O"));
+
+        let sgf = game.to_sgf();
+        assert_eq!(sgf, "(;GM[1]FF[4]CA[UTF-8]SZ[5]AB[aa][bb]AW[cc];B[dd];AW[ee]AE[aa][dd]C[Edited];W[ba])\n");
+        assert_eq!(Game::parse_sgf(&sgf), game);
+
+        let mut cursor = game.cursor();
+        assert!(cursor.forward() && cursor.forward());
+        assert_eq!(cursor.board()[(0, 0)], Stone::Empty);
+        assert!(cursor.back());
+        assert_eq!(cursor.board()[(0, 0)], Stone::Black);
+        assert_eq!(cursor.board()[(3, 3)], Stone::Black);
+
+        assert!(game.undo());
+        assert_eq!(game.moves().len(), 1);
+        assert!(game.undo());
+        assert_eq!(game.board()[(0, 0)], Stone::Black);
+        assert_eq!(game.board()[(4, 4)], Stone::Empty);
+        assert!(game.undo());
+        assert_eq!(game.moves().len(), 0);
+        assert_eq!(game.nodes().len(), 1);
+        assert!(!game.undo());
+
+        // Setup stones may come before the board size.
+        let game = Game::parse_sgf("(;AB[aa][ss]SZ[9])");
+        assert_eq!(game.board().size, 9);
+        assert_eq!(game.board()[(0, 0)], Stone::Black);
+        assert_eq!(game.board().points().filter(|&p| game.board()[p] == Stone::Black).count(), 1);
+
+        let game = Game::parse_sgf("(;AB[aa][ss]SZ[19])");
+        assert_eq!(game.board()[(18, 18)], Stone::Black);
+
+        // The board size can't change in the middle of a game.
+        let game = Game::parse_sgf("(;SZ[9];B[ee];SZ[5];W[aa])");
+        assert_eq!(game.board().size, 9);
+        assert_eq!(game.board()[(4, 4)], Stone::Black);
+        assert_eq!(game.board()[(0, 0)], Stone::White);
+        assert_eq!(game.to_sgf(), "(;GM[1]FF[4]CA[UTF-8]SZ[9];B[ee];;W[aa])\n");
+    }
+
+    #[test]
+    fn sgf_passes() {
+        let mut game = Game::parse_sgf("(;SZ[9];B[cc];W[];B[dd];W[tt];B[ee])");
+
+        assert_eq!(game.nodes().len(), 6);
+        assert_eq!(game.moves().len(), 3);
+        assert_eq!(game.nodes()[2].play(), Some(Play::Pass(Stone::White)));
+        assert_eq!(game.nodes()[4].play(), Some(Play::Pass(Stone::White)));
+        assert_eq!(game.to_sgf(), "(;GM[1]FF[4]CA[UTF-8]SZ[9];B[cc];W[];B[dd];W[];B[ee])\n");
+        assert_eq!(game.captures(Stone::White), 0);
+
+        // Undoing a pass leaves the moves before it alone.
+        assert!(game.undo() && game.undo());
+        assert_eq!(game.node().play(), Some(Play::Move(Move { stone: Stone::Black, point: Point::new(3, 3) })));
+        assert_eq!(game.moves().len(), 2);
+
+        game.pass(Stone::White);
+        assert_eq!(game.nodes().len(), 5);
+        assert_eq!(game.board()[(3, 3)], Stone::Black);
+        assert!(game.undo());
+        assert_eq!(game.board()[(3, 3)], Stone::Black);
+    }
+
+    #[test]
+    fn sgf_illegal_moves() {
+        // White's move is on an occupied point, so it's kept without changing the board.
+        let game = Game::parse_sgf("(;SZ[9];B[cc];W[cc];B[dd])");
+
+        assert_eq!(game.nodes().len(), 4);
+        assert_eq!(game.moves().len(), 2);
+        assert!(game.nodes()[2].is_illegal());
+        assert_eq!(game.nodes()[2].play(), Some(Play::Move(Move { stone: Stone::White, point: Point::new(2, 2) })));
+        assert_eq!(game.board()[(2, 2)], Stone::Black);
+
+        let mut cursor = game.cursor();
+        assert!(cursor.forward() && cursor.forward());
+        assert_eq!(cursor.move_number(), 1);
+        assert_eq!(cursor.board()[(2, 2)], Stone::Black);
+
+        let sgf = game.to_sgf();
+        assert_eq!(sgf, "(;GM[1]FF[4]CA[UTF-8]SZ[9];B[cc];W[cc];B[dd])\n");
+        assert_eq!(Game::parse_sgf(&sgf), game);
+    }
+
+    #[test]
+    fn compressed_setup() {
+        let game = Game::parse_sgf("(;SZ[9]AB[aa:ib]AW[ac:ic][ee];AE[ba:cb])");
+
+        assert_eq!(game.cursor().board().points().filter(|&p| game.cursor().board()[p] == Stone::Black).count(), 18);
+        assert_eq!(game.board()[(1, 0)], Stone::Empty);
+        assert_eq!(game.board()[(2, 1)], Stone::Empty);
+        assert_eq!(game.board()[(3, 1)], Stone::Black);
+        assert_eq!(game.board()[(4, 4)], Stone::White);
+        assert_eq!(game.nodes()[1].setup().len(), 4);
+
+        let compressed = game.write_sgf(&SgfOptions { compress_points: true });
+        assert_eq!(compressed, "(;GM[1]FF[4]CA[UTF-8]SZ[9]AB[aa:ib]AW[ac:ic][ee];AE[ba:cb])\n");
+        assert_eq!(Game::parse_sgf(&compressed), game);
+        assert!(game.to_sgf().contains("AE[ba][ca][bb][cb]"));
+    }
+
+    #[test]
+    fn setup_and_undo() {
+        let mut game = Game::from_str("\
+            ... \
+            ... \
+            ...");
+
+        game.setup(&[(Point::new(0, 0), Stone::Black)]).unwrap();
+        assert_eq!(game.nodes().len(), 1);
+        assert!(!game.undo());
+
+        game.make_move(Stone::White, (1, 1));
+        game.setup(&[(Point::new(1, 1), Stone::Empty), (Point::new(2, 2), Stone::White)]).unwrap();
+        assert_eq!(game.nodes().len(), 3);
+        assert_eq!(game.board(), &Board::from_str("#.. ... ..O"));
+
+        assert_eq!(game.setup(&[(Point::new(3, 0), Stone::Black)]), Err(IllegalMove::OutOfBounds));
+        assert_eq!(game.nodes().len(), 3);
+
+        assert!(game.undo());
+        assert_eq!(game.board(), &Board::from_str("#.. .O. ..."));
+        assert!(game.undo());
+        assert_eq!(game.board(), &Board::from_str("#.. ... ..."));
+        assert!(game.history().is_empty());
+    }
+
+    #[test]
+    fn clock_and_timeout() {
+        let mut game = Game::new();
+        game.set_clock(TimeSettings { main_time: secs(60), control: TimeControl::ByoYomi { periods: 1, time: secs(30) } });
+
+        game.make_move(Stone::Black, (3, 3));
+        assert_eq!(game.record_time(Stone::Black, secs(20)), Ok(()));
+        game.make_move(Stone::White, (15, 15));
+        assert_eq!(game.record_time(Stone::White, secs(70)), Ok(()));
+
+        assert_eq!(game.node().white_time, Some(TimeLeft { time: secs(30), periods: Some(1) }));
+        assert_eq!(game.result(), None);
+
+        let sgf = game.to_sgf();
+        assert_eq!(sgf, "(;GM[1]FF[4]CA[UTF-8]SZ[19]TM[60]OT[1x30 byo-yomi];B[dd]BL[40];W[pp]WL[30]OW[1])\n");
+
+        let mut resumed = Game::parse_sgf(&sgf);
+        assert_eq!(resumed, game);
+
+        assert_eq!(resumed.check_time(Stone::Black, secs(69)), Ok(()));
+        assert_eq!(resumed.check_time(Stone::Black, secs(71)), Err(Timeout(Stone::Black)));
+        assert_eq!(resumed.result(), Some(GameResult::Time(Stone::White)));
+        assert!(resumed.to_sgf().contains("RE[W+T]"));
+
+        // Times too large for a duration are dropped.
+        let game = Game::parse_sgf("(;TM[inf]OT[5x99999999999999999999999 byo-yomi];B[dd]BL[1e30])");
+        assert_eq!(game.clock(), None);
+        assert_eq!(game.node().black_time, None);
+    }
+
+    #[test]
+    fn komi_and_captures() {
+        let mut game = Game::from_str("\
+            O.#O... \
+            #.#O... \
+            ..#O... \
+            ..#O.#. \
+            ..#O... \
+            ..#O... \
+            ..#O...");
+
+        game.set_komi(0.5);
+        assert!(game.make_move(Stone::Black, (1, 0)));
+        assert_eq!(game.captures(Stone::Black), 1);
+        assert_eq!(game.captures(Stone::White), 0);
+
+        // Black has 12 points of territory and a capture, while white has 20 points, and the
+        // dead stone counts as both a point of territory and a capture.
+        assert_eq!(game.estimate_score(), 13.0 - 22.0 - 0.5);
+
+        let sgf = game.to_sgf();
+        assert!(sgf.contains("KM[0.5]"));
+        assert_eq!(Game::parse_sgf(&sgf).komi(), 0.5);
+
+        assert!(game.undo());
+        assert_eq!(game.captures(Stone::Black), 0);
+    }
+
+    #[test]
+    fn seki_points_are_not_territory() {
+        let game = Game::from_str("\
+            .O.#O.. \
+            #O##O.. \
+            ###OO.. \
+            OOOO... \
+            ....... \
+            ....... \
+            .......");
+
+        // The two liberties shared by the chains in seki don't count for either player, while
+        // the other 30 empty points are white's.
+        assert_eq!(game.estimate_score(), -30.0);
+    }
+
+    #[test]
+    fn format_with_last_move() {
+        let mut game = Game::from_str("\
+            ... \
+            ... \
+            ...");
+
+        assert_eq!(game.last_move(), None);
+
+        game.make_move(Stone::Black, (1, 1));
+        assert_eq!(game.last_move(), Some(Move { stone: Stone::Black, point: Point::new(1, 1) }));
+
+        let formatter = BoardFormatter {
+            ascii: true,
+            last_move: game.last_move().map(|m| m.point),
+            ..Default::default()
+        };
+
+        assert_eq!(game.format(&formatter), "\
+            Black Player: <unknown>\n\
+            White Player: <unknown>\n\
+            . . .\n\
+            .(X).\n\
+            . . .");
+        assert_eq!(game.format(&BoardFormatter::new()), format!("{}", game));
+    }
+
+    #[test]
+    fn legal_move_list() {
+        let mut game = Game::from_str("\
+            .#O. \
+            #O.O \
+            .#O. \
+            ....");
+
+        assert!(game.make_move(Stone::Black, (2, 1)));
+
+        // White can play anywhere on the board except for retaking the ko at (1, 1).
+        let moves: Vec<Point> = game.legal_moves(Stone::White).collect();
+        assert!(!moves.contains(&Point::new(1, 1)));
+        assert!(!moves.contains(&Point::new(0, 0)));
+        assert_eq!(moves.len(), 7);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_game() {
+        use serde_json;
+
+        let mut game = Game::from_str("\
+            .#O. \
+            #O.O \
+            .#O. \
+            ....");
+
+        assert!(game.make_move(Stone::Black, (2, 1)));
+        assert!(game.make_move(Stone::White, (3, 3)));
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(json, concat!(
+            r##"{"black":{"name":null,"rank":null},"white":{"name":null,"rank":null},"##,
+            r##""komi":0.0,"result":null,"time_settings":null,"##,
+            r##""setup":{"size":4,"rows":[".#O.","#O.O",".#O.","...."]},"##,
+            r##""nodes":[{},{"play":{"move":{"stone":"black","point":{"x":2,"y":1}}}},"##,
+            r##"{"play":{"move":{"stone":"white","point":{"x":3,"y":3}}}}]}"##));
+
+        // Deserializing replays the moves, restoring the history needed for the ko rule.
+        let restored: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.history, game.history);
+        assert_eq!(restored, game);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_game_record() {
+        use serde_json;
+
+        let game = Game::parse_sgf("(;SZ[9]KM[6.5]RE[B+R]TM[60]OT[5 fischer]C[hi]\
+            ;B[cc]BL[30];AW[ee];W[dd]TR[aa]WL[45];B[])");
+
+        let json = serde_json::to_string(&game).unwrap();
+        let restored: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, game);
+        assert_eq!(restored.nodes()[2].setup(), &[(Point::new(4, 4), Stone::White)][..]);
+        assert_eq!(restored.komi(), 6.5);
+        assert_eq!(restored.result(), Some(GameResult::Resignation(Stone::Black)));
+        assert_eq!(restored.nodes()[0].comment, Some("hi".to_string()));
+        assert_eq!(restored.clock().map(|c| c.time_left(Stone::White).time), Some(secs(45)));
+        assert_eq!(restored.node().play(), Some(Play::Pass(Stone::Black)));
+        assert_eq!(restored.to_sgf(), game.to_sgf());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_illegal_game() {
+        use serde_json;
+
+        let json = concat!(
+            r##"{"black":{"name":null,"rank":null},"white":{"name":null,"rank":null},"##,
+            r##""setup":{"size":2,"rows":["..",".."]},"##,
+            r##""nodes":[{},{"play":{"move":{"stone":"black","point":{"x":0,"y":0}}}},"##,
+            r##"{"play":{"move":{"stone":"white","point":{"x":0,"y":0}}}}]}"##);
+
+        let error = serde_json::from_str::<Game>(json).unwrap_err();
+        assert!(error.to_string().contains("in node 2 is illegal"));
+
+        let json = concat!(
+            r##"{"black":{"name":null,"rank":null},"white":{"name":null,"rank":null},"##,
+            r##""setup":{"size":2,"rows":["..",".."]},"##,
+            r##""nodes":[{},{"setup":[[{"x":2,"y":0},"white"]]}]}"##);
+
+        assert!(serde_json::from_str::<Game>(json).is_err());
+    }
 }
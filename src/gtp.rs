@@ -0,0 +1,453 @@
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use board::{Board, Stone};
+use clock::{Clock, TimeControl, TimeLeft, TimeSettings};
+use game::Game;
+use point::Point;
+use text::BoardFormatter;
+
+/// The commands understood by `Gtp`, as listed by `list_commands`.
+const COMMANDS: &[&str] = &[
+    "boardsize",
+    "clear_board",
+    "final_status_list",
+    "joseki-ladder",
+    "kgs-time_settings",
+    "known_command",
+    "komi",
+    "list_commands",
+    "name",
+    "play",
+    "protocol_version",
+    "quit",
+    "showboard",
+    "time_left",
+    "time_settings",
+    "undo",
+    "version",
+];
+
+/// A Go Text Protocol (version 2) front-end to a `Game`.
+#[derive(Clone, Debug, Default)]
+pub struct Gtp {
+    game: Game,
+    komi: f32,
+    time_settings: Option<TimeSettings>,
+    quit: bool,
+}
+
+impl Gtp {
+    /// Creates a front-end to a new game.
+    pub fn new() -> Gtp {
+        Gtp { ..Default::default() }
+    }
+
+    /// Returns the game being played.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Returns the komi set by the controller.
+    pub fn komi(&self) -> f32 {
+        self.komi
+    }
+
+    /// Returns how long to think about the next move for `stone`, or `None` if the game isn't
+    /// played with time limits.
+    pub fn time_for_move(&self, stone: Stone) -> Option<Duration> {
+        self.game.clock().map(|clock| clock.allocate(stone, self.game.board()))
+    }
+
+    /// Returns whether the controller has sent `quit`.
+    pub fn has_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Handles a line of input, returning the response to send back (or `None` if the line has
+    /// no command in it).
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        // Comments and control characters are removed, and tabs are treated as spaces.
+        let line: String = line.split('#').next().unwrap_or("").chars()
+            .filter(|&c| c == '\t' || !c.is_control())
+            .map(|c| if c == '\t' { ' ' } else { c })
+            .collect();
+
+        let mut words = line.split_whitespace().peekable();
+        let id = words.peek().filter(|w| w.chars().all(|c| c.is_ascii_digit())).map(|w| w.to_string());
+
+        if id.is_some() {
+            words.next();
+        }
+
+        let command = words.next()?;
+        let args: Vec<&str> = words.collect();
+        let id = id.unwrap_or_default();
+
+        Some(match self.execute(command, &args) {
+            Ok(ref response) if response.is_empty() => format!("={}\n\n", id),
+            Ok(response) => format!("={} {}\n\n", id, response),
+            Err(error) => format!("?{} {}\n\n", id, error),
+        })
+    }
+
+    /// Reads commands from `input` until it ends or `quit` is received, writing the responses
+    /// to `output`.
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> io::Result<()> {
+        for line in input.lines() {
+            if let Some(response) = self.handle(&line?) {
+                output.write_all(response.as_bytes())?;
+                output.flush()?;
+            }
+
+            if self.quit {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "protocol_version" => Ok("2".to_string()),
+            "name" => Ok("joseki".to_string()),
+            "version" => Ok(env!("CARGO_PKG_VERSION").to_string()),
+            "known_command" => Ok(COMMANDS.contains(&arg(args, 0)?).to_string()),
+            "list_commands" => Ok(COMMANDS.join("\n")),
+            "quit" => {
+                self.quit = true;
+                Ok(String::new())
+            },
+            "boardsize" => {
+                let size: usize = number(args, 0)?;
+
+                if size == 0 || size > 25 {
+                    return Err("unacceptable size".to_string());
+                }
+
+                self.game.board = Board::with_size(size);
+                self.clear_board();
+                Ok(String::new())
+            },
+            "clear_board" => {
+                self.clear_board();
+                Ok(String::new())
+            },
+            "komi" => {
+                self.komi = arg(args, 0)?.parse().map_err(|_| "syntax error".to_string())?;
+                self.game.set_komi(self.komi);
+                Ok(String::new())
+            },
+            "play" => {
+                let stone = color(arg(args, 0)?)?;
+                let vertex = arg(args, 1)?;
+
+                if vertex.eq_ignore_ascii_case("pass") {
+                    self.game.pass(stone);
+                    return Ok(String::new());
+                }
+
+                let point = Point::from_gtp(vertex, self.game.board().size)
+                    .ok_or_else(|| "illegal move".to_string())?;
+
+                self.game.try_move(stone, point).map_err(|_| "illegal move".to_string())?;
+                Ok(String::new())
+            },
+            "undo" => {
+                if self.game.undo() { Ok(String::new()) } else { Err("cannot undo".to_string()) }
+            },
+            "showboard" => {
+                let formatter = BoardFormatter { coordinates: true, ascii: true, ..Default::default() };
+                Ok(format!("\n{}", formatter.format(self.game.board())))
+            },
+            "time_settings" => {
+                let main_time = seconds(args, 0)?;
+                let period = seconds(args, 1)?;
+                let stones = number(args, 2)?;
+
+                // A period without any stones means there are no time limits at all.
+                self.set_time_settings(match (period == Duration::default(), stones) {
+                    (true, _) => Some(TimeSettings::absolute(main_time)),
+                    (false, 0) => None,
+                    (false, _) => Some(TimeSettings {
+                        main_time,
+                        control: TimeControl::Canadian { stones, time: period },
+                    }),
+                });
+
+                Ok(String::new())
+            },
+            "kgs-time_settings" => {
+                let settings = match arg(args, 0)? {
+                    "none" => None,
+                    "absolute" => Some(TimeSettings::absolute(seconds(args, 1)?)),
+                    "byoyomi" => Some(TimeSettings {
+                        main_time: seconds(args, 1)?,
+                        control: TimeControl::ByoYomi { periods: number(args, 3)?, time: seconds(args, 2)? },
+                    }),
+                    "canadian" => Some(TimeSettings {
+                        main_time: seconds(args, 1)?,
+                        control: TimeControl::Canadian { stones: number(args, 3)?, time: seconds(args, 2)? },
+                    }),
+                    _ => return Err("syntax error".to_string()),
+                };
+
+                self.set_time_settings(settings);
+                Ok(String::new())
+            },
+            "time_left" => {
+                let stone = color(arg(args, 0)?)?;
+                let time = seconds(args, 1)?;
+                let periods = number(args, 2)?;
+
+                // Zero stones (or periods) means the player is still in their main time.
+                let time_left = TimeLeft { time, periods: Some(periods).filter(|&n| n > 0) };
+
+                match self.game.clock {
+                    Some(ref mut clock) => clock.set_time_left(stone, time_left),
+                    None => return Err("no time settings".to_string()),
+                }
+
+                Ok(String::new())
+            },
+            "final_status_list" => {
+                let board = self.game.board();
+                let seki = board.seki();
+                let dead = board.dead_stones_with_seki(&seki);
+
+                let status = |p: &Point| match () {
+                    _ if dead.contains(p) => "dead",
+                    _ if seki.contains(p) => "seki",
+                    _ => "alive",
+                };
+
+                let wanted = arg(args, 0)?;
+
+                if !["dead", "alive", "seki"].contains(&wanted) {
+                    return Err("syntax error".to_string());
+                }
+
+                // Each chain is listed on its own line.
+                let chains: Vec<String> = board.chains()
+                    .filter(|chain| chain.stones().iter().next().map(status) == Some(wanted))
+                    .map(|chain| {
+                        let mut stones: Vec<Point> = chain.stones().iter().cloned().collect();
+                        stones.sort_by_key(|p| (p.y, p.x));
+                        stones.iter().filter_map(|p| p.to_gtp(board.size)).collect::<Vec<_>>().join(" ")
+                    })
+                    .collect();
+
+                Ok(chains.join("\n"))
+            },
+            "joseki-ladder" => {
+                let board = self.game.board();
+                let point = Point::from_gtp(arg(args, 0)?, board.size).ok_or_else(|| "syntax error".to_string())?;
+                let ladder = board.read_ladder(point)
+                    .ok_or_else(|| "not a chain with one or two liberties".to_string())?;
+
+                Ok(ladder.describe(board.size))
+            },
+            _ => Err("unknown command".to_string()),
+        }
+    }
+
+    /// Starts a new game on a board of the same size, keeping the komi and time settings.
+    fn clear_board(&mut self) {
+        let size = self.game.board().size;
+
+        self.game = Game::new();
+        self.game.board = Board::with_size(size);
+        self.game.set_komi(self.komi);
+        self.game.clock = self.time_settings.map(Clock::new);
+    }
+
+    fn set_time_settings(&mut self, settings: Option<TimeSettings>) {
+        self.time_settings = settings;
+        self.game.clock = settings.map(Clock::new);
+    }
+}
+
+fn arg<'a>(args: &[&'a str], index: usize) -> Result<&'a str, String> {
+    args.get(index).cloned().ok_or_else(|| "syntax error".to_string())
+}
+
+fn number<T: FromStr>(args: &[&str], index: usize) -> Result<T, String> {
+    arg(args, index)?.parse().map_err(|_| "syntax error".to_string())
+}
+
+fn seconds(args: &[&str], index: usize) -> Result<Duration, String> {
+    arg(args, index)?.parse().ok()
+        .and_then(|s| Duration::try_from_secs_f64(s).ok())
+        .ok_or_else(|| "syntax error".to_string())
+}
+
+fn color(text: &str) -> Result<Stone, String> {
+    match text.to_lowercase().as_str() {
+        "b" | "black" => Ok(Stone::Black),
+        "w" | "white" => Ok(Stone::White),
+        _ => Err("syntax error".to_string()),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Gtp;
+    use board::Stone;
+    use clock::{TimeControl, TimeLeft, TimeSettings};
+    use point::Point;
+
+    #[test]
+    fn responses() {
+        let mut gtp = Gtp::new();
+
+        assert_eq!(gtp.handle("protocol_version"), Some("= 2\n\n".to_string()));
+        assert_eq!(gtp.handle("12 name # comment"), Some("=12 joseki\n\n".to_string()));
+        assert_eq!(gtp.handle("known_command\tkomi"), Some("= true\n\n".to_string()));
+        assert_eq!(gtp.handle("  # just a comment"), None);
+        assert_eq!(gtp.handle("quit"), Some("=\n\n".to_string()));
+        assert!(gtp.has_quit());
+    }
+
+    #[test]
+    fn play_and_undo() {
+        let mut gtp = Gtp::new();
+
+        assert_eq!(gtp.handle("boardsize 9"), Some("=\n\n".to_string()));
+        assert_eq!(gtp.handle("komi 6.5"), Some("=\n\n".to_string()));
+        assert_eq!(gtp.handle("play black E5"), Some("=\n\n".to_string()));
+        assert_eq!(gtp.handle("play w e5"), Some("? illegal move\n\n".to_string()));
+        assert_eq!(gtp.handle("play w pass"), Some("=\n\n".to_string()));
+        assert_eq!(gtp.komi(), 6.5);
+        assert_eq!(gtp.game().komi(), 6.5);
+        assert_eq!(gtp.game().board()[Point::new(4, 4)], Stone::Black);
+
+        // The pass is undone first, and then black's move.
+        assert_eq!(gtp.handle("undo"), Some("=\n\n".to_string()));
+        assert_eq!(gtp.game().board()[Point::new(4, 4)], Stone::Black);
+        assert_eq!(gtp.handle("undo"), Some("=\n\n".to_string()));
+        assert_eq!(gtp.game().board()[Point::new(4, 4)], Stone::Empty);
+        assert_eq!(gtp.handle("undo"), Some("? cannot undo\n\n".to_string()));
+        assert_eq!(gtp.handle("boardsize 42"), Some("? unacceptable size\n\n".to_string()));
+    }
+
+    #[test]
+    fn final_status_list() {
+        let mut gtp = Gtp::new();
+        gtp.handle("boardsize 7");
+
+        for row in 1..8 {
+            gtp.handle(&format!("play b c{}", row));
+            gtp.handle(&format!("play w d{}", row));
+        }
+
+        gtp.handle("play w a6");
+
+        assert_eq!(gtp.handle("final_status_list dead"), Some("= A6\n\n".to_string()));
+        assert_eq!(gtp.handle("final_status_list alive"),
+            Some("= C7 C6 C5 C4 C3 C2 C1\nD7 D6 D5 D4 D3 D2 D1\n\n".to_string()));
+        assert_eq!(gtp.handle("final_status_list seki"), Some("=\n\n".to_string()));
+        assert_eq!(gtp.handle("final_status_list maybe"), Some("? syntax error\n\n".to_string()));
+    }
+
+    #[test]
+    fn final_status_list_seki() {
+        let mut gtp = Gtp::new();
+        gtp.handle("boardsize 7");
+
+        let rows = [".O.#O..", "#O##O..", "###OO..", "OOOO..."];
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let vertex = Point::new(x, y).to_gtp(7).unwrap();
+
+                match c {
+                    '#' => gtp.handle(&format!("play b {}", vertex)),
+                    'O' => gtp.handle(&format!("play w {}", vertex)),
+                    _ => None,
+                };
+            }
+        }
+
+        assert_eq!(gtp.handle("final_status_list seki"),
+            Some("= B7 B6\nD7 A6 C6 D6 A5 B5 C5\n\n".to_string()));
+        assert_eq!(gtp.handle("final_status_list dead"), Some("=\n\n".to_string()));
+        assert_eq!(gtp.handle("final_status_list alive"),
+            Some("= E7 E6 D5 E5 A4 B4 C4 D4\n\n".to_string()));
+    }
+
+    #[test]
+    fn ladder() {
+        let mut gtp = Gtp::new();
+        gtp.handle("boardsize 9");
+
+        for vertex in &["e5", "f5", "d4", "e3"] {
+            gtp.handle(&format!("play b {}", vertex));
+        }
+
+        gtp.handle("play w e4");
+
+        let response = gtp.handle("joseki-ladder e4").unwrap();
+        assert!(response.starts_with("= captured\npath: F4 "));
+        assert!(!response.contains("breakers"));
+
+        gtp.handle("play w h2");
+
+        let response = gtp.handle("joseki-ladder e4").unwrap();
+        assert!(response.starts_with("= escapes\npath: F4 "));
+        assert!(response.ends_with("\nbreakers: H2\n\n"));
+
+        assert_eq!(gtp.handle("joseki-ladder a1"), Some("? not a chain with one or two liberties\n\n".to_string()));
+        assert_eq!(gtp.handle("joseki-ladder z9"), Some("? syntax error\n\n".to_string()));
+    }
+
+    #[test]
+    fn time_settings() {
+        let mut gtp = Gtp::new();
+        assert_eq!(gtp.time_for_move(Stone::Black), None);
+
+        gtp.handle("time_settings 300 60 25");
+        let settings = TimeSettings {
+            main_time: Duration::from_secs(300),
+            control: TimeControl::Canadian { stones: 25, time: Duration::from_secs(60) },
+        };
+        assert_eq!(gtp.game().clock().map(|c| *c.settings()), Some(settings));
+
+        gtp.handle("time_settings 0 1 0");
+        assert_eq!(gtp.game().clock(), None);
+
+        gtp.handle("kgs-time_settings byoyomi 600 30 5");
+        let settings = TimeSettings {
+            main_time: Duration::from_secs(600),
+            control: TimeControl::ByoYomi { periods: 5, time: Duration::from_secs(30) },
+        };
+        assert_eq!(gtp.game().clock().map(|c| *c.settings()), Some(settings));
+
+        // Time settings are kept for the next game.
+        gtp.handle("clear_board");
+        assert_eq!(gtp.game().clock().map(|c| *c.settings()), Some(settings));
+
+        assert_eq!(gtp.handle("time_left white 20 3"), Some("=\n\n".to_string()));
+        let clock = gtp.game().clock().unwrap();
+        assert_eq!(clock.time_left(Stone::White), TimeLeft { time: Duration::from_secs(20), periods: Some(3) });
+        assert_eq!(gtp.time_for_move(Stone::White), Some(Duration::from_millis(19500)));
+
+        // Times that don't fit in a duration are rejected.
+        assert_eq!(gtp.handle("time_settings 1e400 0 0"), Some("? syntax error\n\n".to_string()));
+        assert_eq!(gtp.handle("time_left b inf 0"), Some("? syntax error\n\n".to_string()));
+        assert_eq!(gtp.handle("time_left b -1 0"), Some("? syntax error\n\n".to_string()));
+
+        // As are stone and period counts that aren't whole numbers that fit in a `u32`.
+        assert_eq!(gtp.handle("time_settings 300 60 2.5"), Some("? syntax error\n\n".to_string()));
+        assert_eq!(gtp.handle("time_left b 10 -1"), Some("? syntax error\n\n".to_string()));
+        assert_eq!(gtp.handle("kgs-time_settings byoyomi 600 30 4294967296"), Some("? syntax error\n\n".to_string()));
+        assert_eq!(gtp.game().clock().map(|c| *c.settings()), Some(settings));
+
+        gtp.handle("kgs-time_settings none");
+        assert_eq!(gtp.handle("time_left b 10 0"), Some("? no time settings\n\n".to_string()));
+    }
+}
@@ -0,0 +1,576 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use board::{Board, Stone};
+use charset;
+use game::{Game, Player};
+use point::Point;
+use result::GameResult;
+
+/// A game record file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Smart Game Format (`.sgf`).
+    Sgf,
+    /// Tygem (`.gib`).
+    Gib,
+    /// WBaduk (`.ngf`).
+    Ngf,
+    /// Pandanet (`.ugf` and `.ugi`).
+    Ugf,
+}
+
+impl Format {
+    /// Guesses the format of a game record from its contents.
+    pub fn detect(contents: &str) -> Option<Format> {
+        let lines: Vec<&str> = contents.lines().map(str::trim).collect();
+
+        if contents.trim_start().starts_with('(') && contents.contains(';') {
+            Some(Format::Sgf)
+        } else if lines.iter().any(|&line| line == "\\HS" || line == "\\GS") {
+            Some(Format::Gib)
+        } else if lines.iter().any(|line| line.eq_ignore_ascii_case("[Header]")) {
+            Some(Format::Ugf)
+        } else if lines.get(1).is_some_and(|line| line.parse::<usize>().is_ok())
+                && lines.iter().any(|line| line.starts_with("PM")) {
+            Some(Format::Ngf)
+        } else {
+            None
+        }
+    }
+}
+
+/// The reason a game record couldn't be read.
+#[derive(Debug)]
+pub enum ImportError {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// The contents don't look like any supported format.
+    UnknownFormat,
+    /// The record is in a known format, but a line of it couldn't be understood.
+    Malformed(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ImportError::Io(ref e) => write!(f, "{}", e),
+            ImportError::UnknownFormat => write!(f, "unknown game record format"),
+            ImportError::Malformed(ref line) => write!(f, "malformed line in game record: {}", line),
+        }
+    }
+}
+
+impl Error for ImportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ImportError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(e: io::Error) -> ImportError {
+        ImportError::Io(e)
+    }
+}
+
+impl Game {
+    /// Reads a game record in any supported format, detected from the contents of the file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Game, ImportError> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+
+        Game::parse_bytes(&bytes)
+    }
+
+    /// Parses a game record in any supported format. SGF records are decoded using the character
+    /// set given by their `CA` property, or as ISO-8859-1 if they aren't UTF-8 and don't declare
+    /// one. GIB and NGF records, which can't declare a character set, are decoded as UTF-8,
+    /// EUC-KR or GBK, whichever fits first.
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Game, ImportError> {
+        match Format::detect(&String::from_utf8_lossy(bytes)) {
+            Some(Format::Gib) | Some(Format::Ngf) => Game::parse(&charset::guess(bytes)),
+            _ => Game::parse(&charset::decode(bytes, charset::sgf_charset(bytes).as_deref())),
+        }
+    }
+
+    /// Parses a game record in any supported format, decoded using the given character set
+    /// (such as `GBK`) unless it's valid UTF-8.
+    pub fn parse_bytes_with_charset(bytes: &[u8], charset: &str) -> Result<Game, ImportError> {
+        Game::parse(&charset::decode(bytes, Some(charset)))
+    }
+
+    /// Parses a game record in any supported format, detected from its contents.
+    pub fn parse(contents: &str) -> Result<Game, ImportError> {
+        match Format::detect(contents) {
+            Some(Format::Sgf) => Ok(Game::parse_sgf(contents)),
+            Some(Format::Gib) => Game::parse_gib(contents),
+            Some(Format::Ngf) => Game::parse_ngf(contents),
+            Some(Format::Ugf) => Game::parse_ugf(contents),
+            None => Err(ImportError::UnknownFormat),
+        }
+    }
+
+    /// Parses a Tygem game record. Moves are given by lines of the form
+    /// `STO 0 <number> <color> <x> <y>`, where the color is `1` for black and `2` for white.
+    /// The komi and result are read from the `GONGJE`, `GRLT` and `ZIPSU` fields of the
+    /// `GAMEINFOMAIN` header, where the komi and margin are given in tenths of a point.
+    pub fn parse_gib(contents: &str) -> Result<Game, ImportError> {
+        let mut game = Game::new();
+
+        for line in contents.lines().map(str::trim) {
+            if let Some(name) = gib_header(line, "GAMEBLACKNAME") {
+                game.black = player(name);
+            } else if let Some(name) = gib_header(line, "GAMEWHITENAME") {
+                game.white = player(name);
+            } else if let Some(info) = gib_header(line, "GAMEINFOMAIN") {
+                let field = |key: &str| gib_field(info, key).and_then(|value| value.parse::<u32>().ok());
+
+                if let Some(komi) = field("GONGJE") {
+                    game.set_komi(komi as f32 / 10.0);
+                }
+
+                game.set_result(match (field("GRLT"), field("ZIPSU")) {
+                    (Some(0), Some(margin)) => Some(GameResult::Score(Stone::Black, margin as f32 / 10.0)),
+                    (Some(1), Some(margin)) => Some(GameResult::Score(Stone::White, margin as f32 / 10.0)),
+                    (Some(3), _) => Some(GameResult::Resignation(Stone::Black)),
+                    (Some(4), _) => Some(GameResult::Resignation(Stone::White)),
+                    (Some(7), _) => Some(GameResult::Time(Stone::Black)),
+                    (Some(8), _) => Some(GameResult::Time(Stone::White)),
+                    _ => None,
+                });
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            match fields.first() {
+                Some(&"INI") => {
+                    let handicap = fields.get(3).and_then(|n| n.parse().ok())
+                        .ok_or_else(|| malformed(line))?;
+                    place_handicap(&mut game.board, handicap);
+                },
+                Some(&"STO") => {
+                    let number = |i: usize| fields.get(i).and_then(|n| n.parse::<usize>().ok());
+
+                    let stone = match number(3) {
+                        Some(1) => Stone::Black,
+                        Some(2) => Stone::White,
+                        _ => return Err(malformed(line)),
+                    };
+
+                    // Coordinates outside of the board are passes.
+                    match (number(4), number(5)) {
+                        (Some(x), Some(y)) => {
+                            let point = Some(Point::new(x, y)).filter(|p| p.in_bounds(game.board.size));
+                            game.replay(stone, point);
+                        },
+                        _ => return Err(malformed(line)),
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Parses a WBaduk game record. The header is a fixed sequence of lines (with the board size
+    /// on the 2nd, the players on the 3rd and 4th, and the handicap on the 6th), followed by
+    /// moves of the form `PM<number><color><x><y>...`, where coordinates start from `B`.
+    pub fn parse_ngf(contents: &str) -> Result<Game, ImportError> {
+        let lines: Vec<&str> = contents.lines().map(str::trim).collect();
+        let header = |i: usize| lines.get(i).cloned().unwrap_or("");
+
+        let size = header(1).parse().map_err(|_| malformed(header(1)))?;
+        let mut game = Game::new();
+        game.board = Board::with_size(size);
+        game.white = spaced_player(header(2));
+        game.black = spaced_player(header(3));
+
+        place_handicap(&mut game.board, header(5).parse().unwrap_or(0));
+
+        for &line in lines.iter().filter(|line| line.starts_with("PM")) {
+            let chars: Vec<char> = line.chars().collect();
+
+            if chars.len() < 7 {
+                return Err(malformed(line));
+            }
+
+            let stone = match chars[4] {
+                'B' => Stone::Black,
+                'W' => Stone::White,
+                _ => return Err(malformed(line)),
+            };
+
+            // Coordinates outside of the board (such as `A`) are passes.
+            let coordinate = |c: char| (c as usize).checked_sub('B' as usize);
+
+            let point = match (coordinate(chars[5]), coordinate(chars[6])) {
+                (Some(x), Some(y)) => Some(Point::new(x, y)).filter(|p| p.in_bounds(size)),
+                _ => None,
+            };
+
+            game.replay(stone, point);
+        }
+
+        Ok(game)
+    }
+
+    /// Parses a Pandanet game record. Moves in the `[Data]` section are of the form
+    /// `<x><y>,<color><...>,<number>,<time>`, where rows are counted upwards from the bottom
+    /// edge. Stones with move number `0` are handicap stones.
+    pub fn parse_ugf(contents: &str) -> Result<Game, ImportError> {
+        let mut game = Game::new();
+        let mut section = String::new();
+        let mut handicap = 0;
+        let mut moves = Vec::new();
+
+        for line in contents.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if line.starts_with('[') {
+                section = line.to_ascii_lowercase();
+                continue;
+            }
+
+            if section == "[header]" {
+                let mut parts = line.splitn(2, '=');
+                let (key, value) = (parts.next().unwrap(), parts.next().unwrap_or(""));
+                let first = value.split(',').next().unwrap_or("").trim();
+
+                match key {
+                    "PlayerB" => game.black = comma_player(value),
+                    "PlayerW" => game.white = comma_player(value),
+                    "Size" => game.board = Board::with_size(first.parse().map_err(|_| malformed(line))?),
+                    "Hdcp" => {
+                        // The handicap is followed by the komi.
+                        handicap = first.parse().unwrap_or(0);
+                        game.set_komi(value.split(',').nth(1)
+                            .and_then(|komi| komi.trim().parse().ok())
+                            .unwrap_or_default());
+                    },
+                    _ => {},
+                }
+            } else if section == "[data]" {
+                let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+                let coordinates: Vec<usize> = fields[0].chars()
+                    .map(|c| (c as usize).wrapping_sub('A' as usize))
+                    .collect();
+
+                let stone = match fields.get(1).and_then(|f| f.chars().next()) {
+                    Some('B') => Stone::Black,
+                    Some('W') => Stone::White,
+                    _ => return Err(malformed(line)),
+                };
+
+                let number: usize = fields.get(2).and_then(|n| n.parse().ok())
+                    .ok_or_else(|| malformed(line))?;
+
+                if coordinates.len() != 2 {
+                    return Err(malformed(line));
+                }
+
+                moves.push((number, stone, coordinates[0], coordinates[1]));
+            }
+        }
+
+        let size = game.board.size;
+
+        if moves.iter().all(|&(number, _, _, _)| number > 0) {
+            place_handicap(&mut game.board, handicap);
+        }
+
+        for (number, stone, x, y) in moves {
+            // Coordinates outside of the board are passes.
+            let point = if x < size && y < size { Some(Point::new(x, size - 1 - y)) } else { None };
+
+            match point {
+                Some(point) if number == 0 => game.board[point] = stone,
+                None if number == 0 => {},
+                point => game.replay(stone, point),
+            }
+        }
+
+        Ok(game)
+    }
+}
+
+fn malformed(line: &str) -> ImportError {
+    ImportError::Malformed(line.to_string())
+}
+
+/// Returns the value of a GIB header line of the form `\[KEY=value\]`.
+fn gib_header<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.strip_prefix("\\[")?
+        .strip_prefix(key)?
+        .strip_prefix('=')?
+        .strip_suffix("\\]")
+}
+
+/// Returns the value of a field of the form `KEY:value` in a comma-separated GIB header.
+fn gib_field<'a>(info: &'a str, key: &str) -> Option<&'a str> {
+    info.split(',')
+        .filter_map(|field| field.trim().strip_prefix(key)?.strip_prefix(':'))
+        .next()
+}
+
+/// Parses a player given as `Name (rank)`.
+fn player(text: &str) -> Player {
+    let text = text.trim();
+
+    match text.rfind(" (") {
+        Some(i) if text.ends_with(')') => Player {
+            name: Some(text[..i].trim().to_string()),
+            rank: Some(text[i + 2..text.len() - 1].to_string()),
+        },
+        _ => Player { name: Some(text.to_string()), rank: None },
+    }
+}
+
+/// Parses a player given as `Name   rank`, where the rank starts with a digit.
+fn spaced_player(text: &str) -> Player {
+    let mut parts = text.rsplitn(2, char::is_whitespace);
+    let last = parts.next().unwrap_or("");
+
+    match parts.next() {
+        Some(name) if last.starts_with(|c: char| c.is_ascii_digit()) => Player {
+            name: Some(name.trim().to_string()),
+            rank: Some(last.to_string()),
+        },
+        _ => Player { name: Some(text.to_string()).filter(|n| !n.is_empty()), rank: None },
+    }
+}
+
+/// Parses a player given as `Name,rank,...`.
+fn comma_player(text: &str) -> Player {
+    let mut parts = text.split(',').map(str::trim).map(String::from);
+
+    Player {
+        name: parts.next().filter(|n| !n.is_empty()),
+        rank: parts.next().filter(|r| !r.is_empty()),
+    }
+}
+
+/// Places `count` black handicap stones on the star points, in the traditional order.
+fn place_handicap(board: &mut Board, count: usize) {
+    if count < 2 || board.size < 7 {
+        return;
+    }
+
+    let edge = if board.size < 13 { 2 } else { 3 };
+    let (low, mid, high) = (edge, board.size / 2, board.size - 1 - edge);
+
+    let corners = [(high, low), (low, high), (high, high), (low, low)];
+    let sides = [(low, mid), (high, mid), (mid, low), (mid, high)];
+
+    let mut points: Vec<(usize, usize)> = corners.iter().take(count).cloned().collect();
+
+    match count {
+        5 | 7 | 9 => {
+            points.extend(sides.iter().take(count - 5));
+            points.push((mid, mid));
+        },
+        6 | 8 => points.extend(sides.iter().take(count - 4)),
+        _ => {},
+    }
+
+    for point in points {
+        board[point] = Stone::Black;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Format, ImportError};
+    use encoding_rs::{EUC_KR, GBK};
+    use board::{Board, Stone};
+    use game::{Game, Play};
+    use point::Point;
+    use result::GameResult;
+
+    const GIB: &str = "\
+\\HS
+\\[GAMEBLACKNAME=Lee Sedol (9D)\\]
+\\[GAMEWHITENAME=Gu Li (9D)\\]
+\\[GAMEINFOMAIN=GBKIND:3,GTYPE:0,GCDT:0,GTIME:1200-30-3,GRLT:1,ZIPSU:55,FIGHTRLT:0,GONGJE:65,\\]
+\\HE
+\\GS
+2 1 0
+119 0 &4
+INI 0 1 0 &4
+STO 0 2 1 15 3
+STO 0 3 2 3 15
+STO 0 4 1 16 15
+\\GE
+";
+
+    const NGF: &str = "\
+Friendly game
+19
+Alice      3D
+Bob        2D
+www.wbaduk.com
+0
+0
+5
+20040101 [12:00]
+5
+Black wins
+3
+PMAABQDDQ
+PMABWDPPD
+PMACBAAAA
+";
+
+    const UGF: &str = "\
+[Header]
+Lang=EN
+PlayerB=Carol,5k,,
+PlayerW=Dave,4k,,
+Size=9
+Hdcp=2,0.5
+
+[Data]
+GG,B1,0,0
+CC,B1,0,0
+EE,W1,1,0
+YA,B1,2,0
+";
+
+    #[test]
+    fn detect() {
+        assert_eq!(Format::detect("(;GM[1]SZ[19];B[pd])"), Some(Format::Sgf));
+        assert_eq!(Format::detect(GIB), Some(Format::Gib));
+        assert_eq!(Format::detect(NGF), Some(Format::Ngf));
+        assert_eq!(Format::detect(UGF), Some(Format::Ugf));
+        assert_eq!(Format::detect("hello\nworld"), None);
+        assert!(matches!(Game::parse("hello"), Err(ImportError::UnknownFormat)));
+    }
+
+    #[test]
+    fn gib() {
+        let game = Game::parse(GIB).unwrap();
+
+        assert_eq!(game.black.name, Some("Lee Sedol".to_string()));
+        assert_eq!(game.black.rank, Some("9D".to_string()));
+        assert_eq!(game.white.name, Some("Gu Li".to_string()));
+        assert_eq!(game.komi(), 6.5);
+        assert_eq!(game.result(), Some(GameResult::Score(Stone::White, 5.5)));
+        assert_eq!(game.moves().len(), 3);
+        assert_eq!(game.board()[(15, 3)], Stone::Black);
+        assert_eq!(game.board()[(3, 15)], Stone::White);
+        assert_eq!(game.board()[(16, 15)], Stone::Black);
+    }
+
+    #[test]
+    fn gib_handicap() {
+        let game = Game::parse_gib("\\GS\nINI 0 1 4 &4\nSTO 0 1 2 9 9\n").unwrap();
+
+        for &point in &[(3, 3), (15, 3), (3, 15), (15, 15)] {
+            assert_eq!(game.history()[0][point], Stone::Black);
+        }
+
+        assert_eq!(game.board()[(9, 9)], Stone::White);
+
+        // Coordinates outside of the board are passes.
+        let game = Game::parse_gib("\\GS\nSTO 0 1 1 3 3\nSTO 0 2 2 19 19\n").unwrap();
+        assert_eq!(game.nodes().len(), 3);
+        assert_eq!(game.node().play(), Some(Play::Pass(Stone::White)));
+        assert!(matches!(Game::parse_gib("STO 0 1 3 9 9"), Err(ImportError::Malformed(_))));
+    }
+
+    #[test]
+    fn ngf() {
+        let game = Game::parse(NGF).unwrap();
+
+        assert_eq!(game.white.name, Some("Alice".to_string()));
+        assert_eq!(game.white.rank, Some("3D".to_string()));
+        assert_eq!(game.black.name, Some("Bob".to_string()));
+        assert_eq!(game.moves().len(), 2);
+        assert_eq!(game.board()[(15, 2)], Stone::Black);
+        assert_eq!(game.board()[(2, 14)], Stone::White);
+        assert_eq!(game.node().play(), Some(Play::Pass(Stone::Black)));
+    }
+
+    #[test]
+    fn ugf() {
+        let game = Game::parse(UGF).unwrap();
+
+        assert_eq!(game.black.name, Some("Carol".to_string()));
+        assert_eq!(game.white.rank, Some("4k".to_string()));
+        assert_eq!(game.board().size, 9);
+        assert_eq!(game.history()[0], Board::from_str("\
+            ......... \
+            ......... \
+            ......#.. \
+            ......... \
+            ......... \
+            ......... \
+            ..#...... \
+            ......... \
+            ........."));
+        assert_eq!(game.moves().len(), 1);
+        assert_eq!(game.board()[Point::new(4, 4)], Stone::White);
+        assert_eq!(game.node().play(), Some(Play::Pass(Stone::Black)));
+        assert_eq!(game.komi(), 0.5);
+    }
+
+    #[test]
+    fn sgf_charset() {
+        let mut bytes = b"(;GM[1]FF[4]CA[EUC-KR]SZ[19]PB[".to_vec();
+        bytes.extend_from_slice(&EUC_KR.encode("이세돌").0);
+        bytes.extend_from_slice(b"];B[pd])");
+
+        let game = Game::parse_bytes(&bytes).unwrap();
+        assert_eq!(game.black.name, Some("이세돌".to_string()));
+        assert_eq!(game.moves().len(), 1);
+    }
+
+    #[test]
+    fn guessed_charsets() {
+        let mut bytes = b"\\HS\n\\[GAMEBLACKNAME=".to_vec();
+        bytes.extend_from_slice(&EUC_KR.encode("이세돌 (9D)").0);
+        bytes.extend_from_slice(b"\\]\n\\HE\n\\GS\nSTO 0 1 1 15 3\n\\GE\n");
+
+        let game = Game::parse_bytes(&bytes).unwrap();
+        assert_eq!(game.black.name, Some("이세돌".to_string()));
+        assert_eq!(game.black.rank, Some("9D".to_string()));
+
+        // Records that aren't EUC-KR can be decoded with a given charset instead.
+        let mut bytes = b"\\HS\n\\[GAMEWHITENAME=".to_vec();
+        bytes.extend_from_slice(&GBK.encode("古力").0);
+        bytes.extend_from_slice(b"\\]\n\\HE\n");
+
+        let game = Game::parse_bytes_with_charset(&bytes, "GBK").unwrap();
+        assert_eq!(game.white.name, Some("古力".to_string()));
+    }
+
+    #[test]
+    fn handicap_placement() {
+        let mut board = Board::with_size(19);
+        super::place_handicap(&mut board, 5);
+
+        let stones: Vec<Point> = board.points().filter(|&p| board[p] == Stone::Black).collect();
+        assert_eq!(stones, vec![
+            Point::new(3, 3), Point::new(15, 3), Point::new(9, 9), Point::new(3, 15), Point::new(15, 15),
+        ]);
+
+        let mut board = Board::with_size(19);
+        super::place_handicap(&mut board, 9);
+        assert_eq!(board.points().filter(|&p| board[p] == Stone::Black).count(), 9);
+        assert!(board.points().filter(|&p| board[p] == Stone::Black).all(|p| board.star_point(p)));
+    }
+
+    #[test]
+    fn convert_to_sgf() {
+        let game = Game::parse(UGF).unwrap();
+        let sgf = game.to_sgf();
+
+        assert_eq!(sgf, "(;GM[1]FF[4]CA[UTF-8]SZ[9]PB[Carol]BR[5k]PW[Dave]WR[4k]KM[0.5]AB[gc][cg];W[ee];B[])\n");
+        assert_eq!(Game::parse(&sgf).unwrap().board(), game.board());
+    }
+}
@@ -0,0 +1,303 @@
+use board::{Board, Stone};
+use game::Move;
+use point::Point;
+
+/// Number of positions to read before giving up on a ladder, which is then assumed to fail.
+const MAX_NODES: usize = 10_000;
+
+/// Longest ladder to read, in moves per line of the board (enough to cross it diagonally).
+const MAX_LENGTH: usize = 4;
+
+/// The outcome of reading a ladder (see `Board::read_ladder`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ladder {
+    /// Whether the chain is captured by the ladder.
+    pub captured: bool,
+    /// The main line of the ladder, alternating between the defender and the attacker. If the
+    /// chain is captured, this ends with the capturing move, and otherwise with the move that
+    /// lets the chain escape.
+    pub path: Vec<Move>,
+    /// Stones already on the board that let the chain escape, either because it connects to
+    /// them or because they're captured along the way. Empty if the chain is captured.
+    pub breakers: Vec<Point>,
+}
+
+impl Ladder {
+    /// Describes the ladder on a board of the given `size` as lines of text: whether the chain
+    /// is `captured` or `escapes`, then the path and any breakers as GTP vertices (or SGF
+    /// coordinates on boards too large for GTP).
+    pub fn describe(&self, size: usize) -> String {
+        let vertices = |points: Vec<Point>| {
+            points.iter().map(|p| p.to_gtp(size).unwrap_or_else(|| p.to_sgf())).collect::<Vec<_>>().join(" ")
+        };
+
+        let mut lines = vec![
+            if self.captured { "captured" } else { "escapes" }.to_string(),
+            format!("path: {}", vertices(self.path.iter().map(|m| m.point).collect())),
+        ];
+
+        if !self.breakers.is_empty() {
+            lines.push(format!("breakers: {}", vertices(self.breakers.clone())));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// State shared by the positions read for a single ladder.
+struct Reader {
+    attacker: Stone,
+    defender: Stone,
+    nodes: usize,
+}
+
+impl Board {
+    /// Reads out whether the chain at `point` can be captured in a ladder: a sequence of
+    /// ataris where the defender can only extend (or capture a neighbouring chain in atari).
+    /// A chain in atari is the defender's to move, while for a chain with two liberties the
+    /// attacker moves first. Returns `None` if `point` is empty or the chain has more liberties.
+    ///
+    /// Neither side may immediately retake a ko, and a ladder that takes too long to read is
+    /// assumed to fail.
+    pub fn read_ladder<P: Into<Point>>(&self, point: P) -> Option<Ladder> {
+        let point = point.into();
+        let defender = self.get(point).filter(|&stone| stone != Stone::Empty)?;
+        let mut reader = Reader { attacker: defender.not(), defender, nodes: 0 };
+
+        let (captured, path) = match self.liberties(point).len() {
+            1 => reader.defend(self, None, point, 0),
+            2 => reader.attack(self, None, point, 0),
+            _ => return None,
+        };
+
+        let mut breakers = Vec::new();
+
+        // Breakers are found by playing out the escape, and comparing the chain and the
+        // attacker's stones with the original position.
+        if !captured {
+            let mut board = self.clone();
+            let chain = self.chain_at(point);
+
+            for m in &path {
+                board.make_move(m.stone, m.point);
+            }
+
+            let escaped = board.chain_at(point);
+
+            breakers = self.points()
+                .filter(|p| match self[*p] {
+                    stone if stone == defender => escaped.contains(p) && !chain.contains(p),
+                    Stone::Empty => false,
+                    _ => board[*p] == Stone::Empty,
+                })
+                .collect();
+        }
+
+        Some(Ladder { captured, path, breakers })
+    }
+}
+
+impl Reader {
+    /// Reads the position with the defender to move and their chain at `point` in atari,
+    /// returning whether it's captured along with the main line. `previous` is the position
+    /// before the last move, which can't be repeated by retaking a ko.
+    fn defend(&mut self, board: &Board, previous: Option<&Board>, point: Point, depth: usize) -> (bool, Vec<Move>) {
+        self.nodes += 1;
+
+        let liberty = *board.liberties(point).iter().next().expect("chain isn't in atari");
+        let capture = vec![Move { stone: self.attacker, point: liberty }];
+
+        // The defender can extend from their last liberty, or capture a neighbouring chain
+        // that's in atari itself.
+        let mut escapes = vec![liberty];
+
+        for stone in board.chain_at(point) {
+            for neighbour in board.neighbours(stone) {
+                if board[neighbour] == self.attacker {
+                    let liberties = board.liberties(neighbour);
+
+                    if liberties.len() == 1 {
+                        escapes.extend(liberties);
+                    }
+                }
+            }
+        }
+
+        escapes.sort_by_key(|p| (p.y, p.x));
+        escapes.dedup();
+
+        let mut main_line: Option<Vec<Move>> = None;
+
+        for escape in escapes {
+            let mut next = board.clone();
+
+            if !next.make_move(self.defender, escape) || previous == Some(&next) {
+                continue;
+            }
+
+            let m = Move { stone: self.defender, point: escape };
+            let (captured, line) = match next.liberties(point).len() {
+                1 => (true, vec![Move { stone: self.attacker, point: *next.liberties(point).iter().next().unwrap() }]),
+                2 => self.attack(&next, Some(board), point, depth + 1),
+                _ => (false, Vec::new()),
+            };
+
+            let line: Vec<Move> = Some(m).into_iter().chain(line).collect();
+
+            if !captured {
+                return (false, line);
+            }
+
+            if main_line.as_ref().is_none_or(|main| main.len() < line.len()) {
+                main_line = Some(line);
+            }
+        }
+
+        (true, main_line.unwrap_or(capture))
+    }
+
+    /// Reads the position with the attacker to move and the defender's chain at `point` on
+    /// two liberties, returning whether it's captured along with the main line.
+    fn attack(&mut self, board: &Board, previous: Option<&Board>, point: Point, depth: usize) -> (bool, Vec<Move>) {
+        self.nodes += 1;
+
+        // Long ladders are also cut off, since recapturing a ko could go on forever.
+        if self.nodes > MAX_NODES || depth > board.size * MAX_LENGTH {
+            return (false, Vec::new());
+        }
+
+        let mut liberties: Vec<Point> = board.liberties(point).into_iter().collect();
+        liberties.sort_by_key(|p| (p.y, p.x));
+
+        let mut main_line: Option<Vec<Move>> = None;
+
+        for liberty in liberties {
+            let mut next = board.clone();
+
+            if !next.make_move(self.attacker, liberty) || previous == Some(&next) || next.liberties(point).len() != 1 {
+                continue;
+            }
+
+            let (captured, line) = self.defend(&next, Some(board), point, depth + 1);
+            let line: Vec<Move> = Some(Move { stone: self.attacker, point: liberty }).into_iter().chain(line).collect();
+
+            if captured {
+                return (true, line);
+            }
+
+            if main_line.as_ref().is_none_or(|main| main.len() < line.len()) {
+                main_line = Some(line);
+            }
+        }
+
+        (false, main_line.unwrap_or_default())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use board::{Board, Stone};
+    use game::Move;
+    use point::Point;
+
+    #[test]
+    fn not_a_ladder() {
+        let board = Board::from_str("\
+            ..... \
+            .O... \
+            ..... \
+            ..... \
+            .....");
+
+        assert_eq!(board.read_ladder((0, 0)), None);
+        assert_eq!(board.read_ladder((1, 1)), None);
+    }
+
+    #[test]
+    fn captured_by_ladder() {
+        let board = Board::from_str("\
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            ....##... \
+            ...#O.... \
+            ....#.... \
+            ......... \
+            .........");
+
+        let ladder = board.read_ladder((4, 5)).unwrap();
+        assert!(ladder.captured);
+        assert!(ladder.breakers.is_empty());
+
+        // White runs towards the bottom right corner, until they run out of room.
+        assert_eq!(ladder.path[0], Move { stone: Stone::White, point: Point::new(5, 5) });
+        assert_eq!(ladder.path.len() % 2, 0);
+        assert!(ladder.path.iter().step_by(2).all(|m| m.stone == Stone::White));
+        assert!(ladder.path.iter().skip(1).step_by(2).all(|m| m.stone == Stone::Black));
+
+        let mut played = board.clone();
+
+        for m in &ladder.path {
+            assert!(played.make_move(m.stone, m.point));
+        }
+
+        assert_eq!(played[(4, 5)], Stone::Empty);
+    }
+
+    #[test]
+    fn ladder_breaker() {
+        let board = Board::from_str("\
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            ....##... \
+            ...#O.... \
+            ....#.... \
+            .......O. \
+            .........");
+
+        let ladder = board.read_ladder((4, 5)).unwrap();
+        assert!(!ladder.captured);
+        assert_eq!(ladder.breakers, vec![Point::new(7, 7)]);
+        assert!(ladder.describe(9).starts_with("escapes\npath: F4 "));
+        assert!(ladder.describe(9).ends_with("\nbreakers: H2"));
+    }
+
+    #[test]
+    fn attack_with_two_liberties() {
+        let board = Board::from_str("\
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            ....##... \
+            ...#O.... \
+            ......... \
+            ......... \
+            .........");
+
+        // Black starts the ladder with an atari from below, rather than the side.
+        let ladder = board.read_ladder((4, 5)).unwrap();
+        assert!(ladder.captured);
+        assert_eq!(ladder.path[0], Move { stone: Stone::Black, point: Point::new(4, 6) });
+    }
+
+    #[test]
+    fn escape_by_capturing() {
+        // The black stone at the top is in atari, so white captures it instead of extending.
+        let board = Board::from_str("\
+            .#O.. \
+            #O#.. \
+            ..... \
+            ..... \
+            .....");
+
+        let ladder = board.read_ladder((1, 1)).unwrap();
+        assert!(!ladder.captured);
+        assert_eq!(ladder.path[0], Move { stone: Stone::White, point: Point::new(0, 0) });
+        assert!(ladder.breakers.contains(&Point::new(1, 0)));
+    }
+}
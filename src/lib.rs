@@ -1,8 +1,53 @@
 //! Go/Baduk engine written in Rust.
 
+extern crate encoding_rs;
+#[cfg(feature = "gif")]
+extern crate gif;
+#[cfg(feature = "png")]
+extern crate png;
 extern crate regex;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 
 mod board;
+mod charset;
+mod clock;
+mod collection;
+mod diagram;
+mod eye;
 mod game;
+mod gtp;
+mod import;
+mod ladder;
+mod life;
+mod markup;
+mod node;
+mod ownership;
+mod point;
+mod raster;
+mod result;
+mod seki;
+mod sgf;
+mod svg;
+mod text;
 
-pub use game::Game;
+pub use board::{Board, Chain, Chains, IllegalMove, Points, Stone};
+pub use clock::{Clock, TimeControl, TimeLeft, TimeSettings, Timeout};
+pub use collection::Collection;
+pub use diagram::{Diagram, DiagramError};
+pub use eye::Eye;
+pub use game::{Cursor, Game, Move, Play};
+pub use gtp::Gtp;
+pub use import::{Format, ImportError};
+pub use ladder::Ladder;
+pub use markup::Markup;
+pub use node::{Annotation, Emphasis, Node};
+pub use point::Point;
+pub use raster::{Image, RasterOptions};
+pub use result::GameResult;
+pub use sgf::SgfOptions;
+pub use svg::SvgOptions;
+pub use text::BoardFormatter;
@@ -0,0 +1,543 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use board::{Board, Chain, Stone};
+use eye::Eye;
+use point::Point;
+
+/// Own regions at least this large are assumed to have room for two eyes.
+const LARGE_EYE_SPACE: usize = 6;
+
+/// Stones that own at least this many empty points of the area around them (see
+/// `Board::ownership`) are assumed to have room to make eyes.
+const OWNED_POINTS: usize = 2;
+
+/// Ownership at which an empty point counts towards `OWNED_POINTS`.
+const OWNED: f32 = 0.5;
+
+/// Groups with at most this many liberties can be dead without an area being enclosed around
+/// them, if the opponent's stones surround them closely enough.
+const SURROUNDED_LIBERTIES: usize = 5;
+
+impl Board {
+    /// Returns the maximal connected set of points containing `point` whose contents satisfy
+    /// `include` (such as all of the empty points around it).
+    pub fn region<P, F>(&self, point: P, include: F) -> HashSet<Point>
+        where P: Into<Point>, F: Fn(Point) -> bool
+    {
+        let point = point.into();
+        let mut region = HashSet::new();
+
+        if !include(point) {
+            return region;
+        }
+
+        region.insert(point);
+        let mut horizon = vec![point];
+
+        while let Some(next) = horizon.pop() {
+            for neighbour in self.neighbours(next) {
+                if include(neighbour) && region.insert(neighbour) {
+                    horizon.push(neighbour);
+                }
+            }
+        }
+
+        region
+    }
+
+    /// Returns the set of stones adjacent to (but not part of) `region`.
+    pub fn border(&self, region: &HashSet<Point>) -> HashSet<Point> {
+        region.iter()
+            .flat_map(|&p| self.neighbours(p))
+            .filter(|p| !region.contains(p) && self[*p] != Stone::Empty)
+            .collect()
+    }
+
+    /// Returns the stones of `color` that are unconditionally alive: stones that can never be
+    /// captured, even if `color` passes every turn and the opponent plays as many moves in a
+    /// row as they like.
+    ///
+    /// This is Benson's algorithm. The board is split into regions of points that aren't
+    /// `color`, each enclosed by `color` chains, and a region is vital to a chain if all of its
+    /// empty points are liberties of that chain. Chains with fewer than two vital regions are
+    /// discarded, along with the regions they enclose, until every remaining chain has two
+    /// regions that the opponent can't fill without capturing it first.
+    pub fn unconditionally_alive(&self, color: Stone) -> HashSet<Point> {
+        let chains: Vec<Chain> = self.chains().filter(|c| c.color() == color).collect();
+        let chain_of: HashMap<Point, usize> = chains.iter().enumerate()
+            .flat_map(|(i, chain)| chain.stones().iter().map(move |&p| (p, i)))
+            .collect();
+
+        // Each region is given as the chains enclosing it and the chains it's vital to.
+        let mut regions: Vec<(HashSet<usize>, HashSet<usize>)> = Vec::new();
+        let mut seen = HashSet::new();
+
+        for point in self.points() {
+            if self[point] == color || seen.contains(&point) {
+                continue;
+            }
+
+            let region = self.region(point, |p| self[p] != color);
+            seen.extend(region.iter().cloned());
+
+            let enclosing: HashSet<usize> = self.border(&region).iter()
+                .filter_map(|p| chain_of.get(p).cloned())
+                .collect();
+            let vital = enclosing.iter()
+                .filter(|&&i| region.iter().all(|p| self[*p] != Stone::Empty || chains[i].liberties().contains(p)))
+                .cloned()
+                .collect();
+
+            regions.push((enclosing, vital));
+        }
+
+        let mut alive: HashSet<usize> = (0..chains.len()).collect();
+
+        loop {
+            let healthy: Vec<&HashSet<usize>> = regions.iter()
+                .filter(|&(enclosing, _)| enclosing.is_subset(&alive))
+                .map(|(_, vital)| vital)
+                .collect();
+
+            let remaining: HashSet<usize> = alive.iter()
+                .filter(|i| healthy.iter().filter(|vital| vital.contains(i)).count() >= 2)
+                .cloned()
+                .collect();
+
+            if remaining.len() == alive.len() {
+                break;
+            }
+
+            alive = remaining;
+        }
+
+        alive.into_iter().flat_map(|i| chains[i].stones().iter().cloned()).collect()
+    }
+
+    /// Estimates which stones are dead, for scoring a finished game.
+    ///
+    /// For each color, the board is split into areas enclosed by that color's (living) stones.
+    /// Opposing stones in an area that covers less than half of the board are dead unless they
+    /// have room to live: one of their chains has two eyes (two regions of empty points bordered
+    /// only by their own color, or a single region large enough for two, counting those shared
+    /// with other chains), the wall next to them has a chain in atari, or they own part of the
+    /// area (see `Board::ownership`) or are nearer than the wall to most of it. Groups that the
+    /// opponent surrounds without fully enclosing them are also dead if they have few liberties,
+    /// fewer than two eyes (false eyes don't count), and the opponent would own all of their
+    /// points without them. Unconditionally alive stones (see `Board::unconditionally_alive`)
+    /// and stones in seki (see `Board::seki`) are never dead.
+    /// This is a static estimate, so it's most reliable once the game has been played out and
+    /// the borders are settled.
+    pub fn dead_stones(&self) -> HashSet<Point> {
+        self.dead_stones_with_seki(&self.seki())
+    }
+
+    /// Estimates dead stones as `Board::dead_stones` does, given the stones in seki.
+    pub(crate) fn dead_stones_with_seki(&self, seki: &HashSet<Point>) -> HashSet<Point> {
+        let mut dead = HashSet::new();
+        let mut alive = self.unconditionally_alive(Stone::Black);
+        alive.extend(self.unconditionally_alive(Stone::White));
+        alive.extend(seki.iter().cloned());
+
+        // Stones inside the smallest area are settled first, since dead stones don't take away
+        // the eyes of the chains around them (and don't enclose anything themselves).
+        loop {
+            let mut smallest: Option<(usize, HashSet<Point>)> = None;
+            let ownership = self.ownership_with_dead(&dead);
+
+            for &color in &[Stone::Black, Stone::White] {
+                let wall = |p: Point| self[p] == color;
+                let mut seen = HashSet::new();
+
+                for point in self.points() {
+                    if wall(point) || seen.contains(&point) {
+                        continue;
+                    }
+
+                    let area = self.region(point, |p| !wall(p));
+                    seen.extend(area.iter().cloned());
+
+                    if area.len() * 2 > self.size * self.size
+                            || smallest.as_ref().is_some_and(|&(size, _)| size <= area.len()) {
+                        continue;
+                    }
+
+                    let opponents: HashSet<Point> = area.iter()
+                        .filter(|&&p| self[p] == color.not() && !dead.contains(&p))
+                        .cloned()
+                        .collect();
+
+                    // A wall that's in atari can be captured by the stones next to it.
+                    let living = |p: &Point| {
+                        alive.contains(p) || self.eye_spaces(*p, &dead) >= 2 || self.neighbours(*p).into_iter()
+                            .any(|n| self[n] == color && !alive.contains(&n) && self.liberties(n).len() == 1)
+                    };
+
+                    if opponents.is_empty() || opponents.iter().any(living) {
+                        continue;
+                    }
+
+                    if !self.controls_area(&area, &opponents, &ownership) {
+                        smallest = Some((area.len(), opponents));
+                    }
+                }
+            }
+
+            match smallest {
+                Some((_, stones)) => dead.extend(stones),
+                None => break,
+            }
+        }
+
+        // Groups that aren't quite enclosed by the opponent's stones are dead if they're
+        // surrounded anyway.
+        let surrounded = self.surrounded_groups(&alive, &dead);
+        dead.extend(surrounded);
+        dead
+    }
+
+    /// Returns the stones in groups (chains that are next to each other or share a liberty)
+    /// that have fewer than two eyes and at most `SURROUNDED_LIBERTIES` liberties, aren't next
+    /// to an opposing chain in atari, and whose points would all be owned by the opponent if
+    /// they were taken off the board.
+    fn surrounded_groups(&self, alive: &HashSet<Point>, dead: &HashSet<Point>) -> HashSet<Point> {
+        let mut surrounded = HashSet::new();
+        let mut seen = HashSet::new();
+
+        for point in self.points() {
+            if self[point] == Stone::Empty || dead.contains(&point) || seen.contains(&point) {
+                continue;
+            }
+
+            let color = self[point];
+            let same = |p: Point| self[p] == color && !dead.contains(&p);
+            let mut group: HashSet<Point> = Some(point).into_iter().collect();
+            let mut stones = vec![point];
+
+            while let Some(stone) = stones.pop() {
+                for neighbour in self.neighbours(stone) {
+                    let next = match self[neighbour] {
+                        Stone::Empty => self.neighbours(neighbour),
+                        _ => vec![neighbour],
+                    };
+
+                    for p in next.into_iter().filter(|&p| same(p)) {
+                        if group.insert(p) {
+                            stones.push(p);
+                        }
+                    }
+                }
+            }
+
+            seen.extend(group.iter().cloned());
+
+            let liberties: HashSet<Point> = group.iter()
+                .flat_map(|&p| self.neighbours(p))
+                .filter(|&p| self[p] == Stone::Empty)
+                .collect();
+
+            let racing = group.iter()
+                .flat_map(|&p| self.neighbours(p))
+                .any(|n| self[n] == color.not() && !dead.contains(&n) && self.liberties(n).len() == 1);
+
+            if liberties.len() > SURROUNDED_LIBERTIES || racing
+                    || group.iter().any(|&p| alive.contains(&p) || self.eye_spaces(p, dead) >= 2) {
+                continue;
+            }
+
+            let mut removed = dead.clone();
+            removed.extend(group.iter().cloned());
+
+            let ownership = self.ownership_with_dead(&removed);
+            let sign = if color == Stone::Black { 1.0 } else { -1.0 };
+
+            if group.iter().all(|p| ownership[p.y * self.size + p.x] * sign <= -OWNED) {
+                surrounded.extend(group);
+            }
+        }
+
+        surrounded
+    }
+
+    /// Returns whether `stones` have room to live in `area` (a moyo, rather than territory with a
+    /// few stones inside): either they own some of its empty points outside of their eyes, going
+    /// by `ownership`, or they're nearer than the stones enclosing `area` to at least half of
+    /// those points.
+    fn controls_area(&self, area: &HashSet<Point>, stones: &HashSet<Point>, ownership: &[f32]) -> bool {
+        let distances = |sources: Vec<Point>| {
+            let mut distance: HashMap<Point, usize> = sources.iter().map(|&p| (p, 0)).collect();
+            let mut queue: VecDeque<Point> = sources.into_iter().collect();
+
+            while let Some(point) = queue.pop_front() {
+                let d = distance[&point] + 1;
+
+                for neighbour in self.neighbours(point) {
+                    if area.contains(&neighbour) && !distance.contains_key(&neighbour) {
+                        distance.insert(neighbour, d);
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+
+            distance
+        };
+
+        let inside = distances(stones.iter().cloned().collect());
+        let outside = distances(self.border(area).into_iter().collect());
+
+        // Eye spaces that are already enclosed by the stones don't give them any more room.
+        let color = self[*stones.iter().next().expect("no stones in area")];
+        let mut eyes = HashSet::new();
+
+        for &point in area {
+            if self[point] == Stone::Empty && !eyes.contains(&point) {
+                let space = self.region(point, |p| self[p] == Stone::Empty);
+
+                if self.border(&space).iter().all(|p| self[*p] == color) {
+                    eyes.extend(space);
+                }
+            }
+        }
+
+        let open: Vec<&Point> = area.iter()
+            .filter(|p| self[**p] == Stone::Empty && !eyes.contains(p))
+            .collect();
+        let sign = if color == Stone::Black { 1.0 } else { -1.0 };
+        let owned = open.iter().filter(|p| ownership[p.y * self.size + p.x] * sign >= OWNED).count();
+        let controlled = open.iter().filter(|p| inside.get(p) < outside.get(p)).count();
+
+        owned >= OWNED_POINTS || (!open.is_empty() && controlled * 2 >= open.len())
+    }
+
+    /// Counts the eyes the chain at `point` could make from the regions of empty (or dead)
+    /// points next to it that are bordered only by its own color, other than false eyes (see
+    /// `Board::eye`). Chains that share one of these regions can't be cut apart without filling
+    /// it, so their other eyes are counted too.
+    fn eye_spaces(&self, point: Point, dead: &HashSet<Point>) -> usize {
+        let color = self[point];
+        let open = |p: Point| self[p] == Stone::Empty || dead.contains(&p);
+        let mut group = self.chain_at(point);
+        let mut stones: Vec<Point> = group.iter().cloned().collect();
+        let mut seen = HashSet::new();
+        let mut eyes = 0;
+
+        while let Some(stone) = stones.pop() {
+            for neighbour in self.neighbours(stone) {
+                if !open(neighbour) || seen.contains(&neighbour) {
+                    continue;
+                }
+
+                let space = self.region(neighbour, open);
+                seen.extend(space.iter().cloned());
+
+                let border = self.border(&space);
+
+                // A false eye has to be filled to connect the stones around it, so it's neither
+                // an eye nor a connection between them.
+                if space.len() == 1 && self.eye(neighbour, color) == Some(Eye::False) {
+                    continue;
+                }
+
+                if border.iter().all(|p| self[*p] == color || dead.contains(p)) {
+                    eyes += if space.len() >= LARGE_EYE_SPACE { 2 } else { 1 };
+
+                    for p in border.into_iter().filter(|p| self[*p] == color) {
+                        for q in self.chain_at(p) {
+                            if group.insert(q) {
+                                stones.push(q);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        eyes
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use board::{Board, Stone};
+    use point::Point;
+
+    fn points(board: &Board, stone: Stone) -> HashSet<Point> {
+        board.points().filter(|&p| board[p] == stone).collect()
+    }
+
+    #[test]
+    fn regions() {
+        let board = Board::from_str("\
+            .#O.. \
+            ##O.. \
+            OOO.. \
+            ..... \
+            .....");
+
+        let corner = board.region((0, 0), |p| board[p] == Stone::Empty);
+        assert_eq!(corner, [Point::new(0, 0)].iter().cloned().collect());
+        assert_eq!(board.border(&corner), [Point::new(1, 0), Point::new(0, 1)].iter().cloned().collect());
+
+        let white = board.region((2, 0), |p| board[p] == Stone::White);
+        assert_eq!(white.len(), 5);
+        assert!(board.region((0, 0), |p| board[p] == Stone::White).is_empty());
+    }
+
+    #[test]
+    fn dead_stones_in_territory() {
+        let board = Board::from_str("\
+            ....#O... \
+            .O..#O... \
+            ....#O.#. \
+            ....#O... \
+            ....#O... \
+            O...#O... \
+            ....#O... \
+            ....#O... \
+            ....#O...");
+
+        let expected = [Point::new(1, 1), Point::new(7, 2), Point::new(0, 5)].iter().cloned().collect();
+        assert_eq!(board.dead_stones(), expected);
+    }
+
+    #[test]
+    fn living_groups() {
+        // The black group in the corner has two eyes, so it lives inside white's area.
+        let board = Board::from_str("\
+            .#.#O.... \
+            ####O.... \
+            OOOOO.... \
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            .........");
+
+        assert!(board.dead_stones().is_empty());
+
+        // With only one eye, it's dead.
+        let board = Board::from_str("\
+            ..##O.... \
+            ####O.... \
+            OOOOO.... \
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            .........");
+
+        assert_eq!(board.dead_stones(), points(&board, Stone::Black));
+    }
+
+    #[test]
+    fn false_eyes() {
+        // C9 is a false eye, since white can capture D9 and then black has to fill it, so the
+        // black group only has one eye.
+        let board = Board::from_str("\
+            .#.#O.... \
+            ###OO.... \
+            OOOO..... \
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            .........");
+
+        assert_eq!(board.dead_stones(), points(&board, Stone::Black));
+    }
+
+    #[test]
+    fn surrounded_groups() {
+        // White's stones don't enclose an area around the black group, since it could run out
+        // at F6, but it can't escape or make two eyes.
+        let board = Board::from_str("\
+            ......... \
+            ..OOO.... \
+            .O.#.O... \
+            .O##..... \
+            ..O..O... \
+            ...OO.... \
+            ......... \
+            ......... \
+            .........");
+
+        assert_eq!(board.dead_stones(), points(&board, Stone::Black));
+    }
+
+    #[test]
+    fn unconditional_life() {
+        // Black's two eyes can't be filled, even with a white stone already in one of them.
+        let board = Board::from_str("\
+            O.#.#O. \
+            #####O. \
+            OOOOOO. \
+            ....... \
+            ....... \
+            ....... \
+            .......");
+
+        assert_eq!(board.unconditionally_alive(Stone::Black), points(&board, Stone::Black));
+        assert!(board.unconditionally_alive(Stone::White).is_empty());
+
+        // With a single eye, the black group can be captured.
+        let board = Board::from_str("\
+            ....#O. \
+            #####O. \
+            OOOOOO. \
+            ....... \
+            ....... \
+            ....... \
+            .......");
+
+        assert!(board.unconditionally_alive(Stone::Black).is_empty());
+    }
+
+    #[test]
+    fn regions_enclosed_by_capturable_chains() {
+        // The eye at C7 is shared with the stone at D7, which white can capture, so it doesn't
+        // count towards the life of the group on the left.
+        let board = Board::from_str("\
+            .#.#... \
+            ###O... \
+            OOOO... \
+            ....... \
+            ....... \
+            ....... \
+            .......");
+
+        assert!(board.unconditionally_alive(Stone::Black).is_empty());
+
+        let board = Board::from_str("\
+            .#.#... \
+            ####... \
+            OOOO... \
+            ....... \
+            ....... \
+            ....... \
+            .......");
+
+        assert_eq!(board.unconditionally_alive(Stone::Black), points(&board, Stone::Black));
+    }
+
+    #[test]
+    fn opening_stones_live() {
+        let board = Board::from_str("\
+            ......... \
+            ......... \
+            ..#...O.. \
+            ......... \
+            ....#.... \
+            ......... \
+            ..O...#.. \
+            ......... \
+            .........");
+
+        assert!(board.dead_stones().is_empty());
+    }
+}
@@ -1,15 +1,107 @@
 extern crate joseki;
 
 use std::env;
+use std::io;
+use std::process;
 
-use joseki::Game;
+use joseki::{BoardFormatter, Game, GameResult, Gtp, Point, SgfOptions, Stone};
 
 fn main() {
-    let args: Vec<_> = env::args().collect();
+    if env::args().nth(1).as_deref() == Some("gtp") {
+        let stdin = io::stdin();
 
-    if args.len() <= 1 {
-        eprintln!("Usage: joseki <filename>");
+        if let Err(e) = Gtp::new().run(stdin.lock(), io::stdout()) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+
+        return;
+    }
+
+    if env::args().nth(1).as_deref() == Some("estimate") {
+        let filename = env::args().nth(2).unwrap_or_else(|| usage());
+        let score = open(&filename).estimate_score();
+
+        // The margin is rounded to the nearest tenth of a point.
+        let margin = (score.abs() * 10.0).round() / 10.0;
+        let result = match score {
+            _ if margin == 0.0 => GameResult::Draw,
+            _ if score > 0.0 => GameResult::Score(Stone::Black, margin),
+            _ => GameResult::Score(Stone::White, margin),
+        };
+
+        println!("{}", result);
+        return;
+    }
+
+    if env::args().nth(1).as_deref() == Some("ladder") {
+        let (filename, vertex) = match (env::args().nth(2), env::args().nth(3)) {
+            (Some(filename), Some(vertex)) => (filename, vertex),
+            _ => usage(),
+        };
+
+        let board = open(&filename).board().clone();
+        let ladder = match Point::from_gtp(&vertex, board.size).and_then(|p| board.read_ladder(p)) {
+            Some(ladder) => ladder,
+            None => {
+                eprintln!("{}: not a chain with one or two liberties", vertex);
+                process::exit(1);
+            },
+        };
+
+        println!("{}", ladder.describe(board.size));
+        return;
+    }
+
+    let mut formatter = BoardFormatter { coordinates: true, ..Default::default() };
+    let mut sgf = false;
+    let mut ownership = false;
+    let mut sgf_options = SgfOptions::default();
+    let mut filename = None;
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--ascii" => formatter.ascii = true,
+            "--color" => formatter.color = true,
+            "--compact" => formatter.compact = true,
+            "--no-coordinates" => formatter.coordinates = false,
+            "--ownership" => ownership = true,
+            "--sgf" => sgf = true,
+            "--compress-points" => sgf_options.compress_points = true,
+            _ => filename = Some(arg),
+        }
+    }
+
+    let filename = filename.unwrap_or_else(|| usage());
+    let game = open(&filename);
+
+    if sgf {
+        print!("{}", game.write_sgf(&sgf_options));
     } else {
-        println!("{}", Game::from_sgf(&args[1]));
+        formatter.last_move = game.last_move().map(|m| m.point);
+
+        if ownership {
+            formatter.ownership = Some(game.board().ownership());
+        }
+
+        println!("{}", game.format(&formatter));
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: joseki gtp");
+    eprintln!("       joseki estimate <filename>");
+    eprintln!("       joseki ladder <filename> <vertex>");
+    eprintln!("       joseki [--ascii] [--color] [--compact] [--no-coordinates] [--ownership] [--sgf [--compress-points]] <filename>");
+    process::exit(1);
+}
+
+fn open(filename: &str) -> Game {
+    match Game::open(filename) {
+        Ok(game) => game,
+        Err(e) => {
+            eprintln!("{}: {}", filename, e);
+            process::exit(1);
+        },
     }
 }
@@ -0,0 +1,11 @@
+/// An annotation drawn on top of a point in a diagram.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Markup {
+    Circle,
+    Cross,
+    Square,
+    Triangle,
+    Label(String),
+}
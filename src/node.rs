@@ -0,0 +1,410 @@
+use std::time::Duration;
+
+use board::Stone;
+use clock::TimeLeft;
+use game::{Move, Play};
+use markup::Markup;
+use point::Point;
+use sgf::{self, Property, SgfOptions};
+
+/// Markup properties, in the order they are written.
+const MARKUP_PROPERTIES: &[(&str, Markup)] = &[
+    ("CR", Markup::Circle),
+    ("MA", Markup::Cross),
+    ("SQ", Markup::Square),
+    ("TR", Markup::Triangle),
+];
+
+/// How strongly an annotation applies (SGF's `Double` type).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Emphasis {
+    Normal,
+    Strong,
+}
+
+impl Emphasis {
+    fn from_sgf(value: &str) -> Emphasis {
+        if value.trim() == "2" { Emphasis::Strong } else { Emphasis::Normal }
+    }
+
+    fn to_sgf(self) -> String {
+        if self == Emphasis::Strong { "2" } else { "1" }.to_string()
+    }
+}
+
+/// An evaluation of a move or position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Annotation {
+    /// The move is bad (`BM`).
+    BadMove(Emphasis),
+    /// The move is a tesuji (`TE`).
+    Tesuji(Emphasis),
+    /// The move is doubtful (`DO`).
+    Doubtful,
+    /// The move is interesting (`IT`).
+    Interesting,
+    /// The position is good for black (`GB`).
+    GoodForBlack(Emphasis),
+    /// The position is good for white (`GW`).
+    GoodForWhite(Emphasis),
+    /// The position is even (`DM`).
+    Even(Emphasis),
+    /// The position is unclear (`UC`).
+    Unclear(Emphasis),
+    /// The node is a hotspot, such as a game-deciding move (`HO`).
+    Hotspot(Emphasis),
+    /// The estimated score of the position, positive if black is ahead (`V`).
+    Value(f64),
+}
+
+impl Annotation {
+    /// Returns the SGF property identifier and value for the annotation.
+    fn to_sgf(self) -> (&'static str, String) {
+        match self {
+            Annotation::BadMove(e) => ("BM", e.to_sgf()),
+            Annotation::Tesuji(e) => ("TE", e.to_sgf()),
+            Annotation::Doubtful => ("DO", String::new()),
+            Annotation::Interesting => ("IT", String::new()),
+            Annotation::GoodForBlack(e) => ("GB", e.to_sgf()),
+            Annotation::GoodForWhite(e) => ("GW", e.to_sgf()),
+            Annotation::Even(e) => ("DM", e.to_sgf()),
+            Annotation::Unclear(e) => ("UC", e.to_sgf()),
+            Annotation::Hotspot(e) => ("HO", e.to_sgf()),
+            Annotation::Value(v) => ("V", v.to_string()),
+        }
+    }
+}
+
+/// A position in the record of a game: the move played to reach it (if any), along with
+/// comments and markup describing it.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Node {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    play: Option<Play>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "std::ops::Not::not"))]
+    illegal: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    setup: Vec<(Point, Stone)>,
+    /// A comment on the node (`C`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub comment: Option<String>,
+    /// A short name for the node (`N`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<String>,
+    /// Markup on points (`CR`, `MA`, `SQ`, `TR` and `LB`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub markup: Vec<(Point, Markup)>,
+    /// Points that should be shown dimmed (`DD`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub dimmed: Vec<Point>,
+    /// Arrows from one point to another (`AR`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub arrows: Vec<(Point, Point)>,
+    /// Lines between two points (`LN`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub lines: Vec<(Point, Point)>,
+    /// Evaluations of the move or position.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub annotations: Vec<Annotation>,
+    /// The time black has left (`BL` and `OB`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub black_time: Option<TimeLeft>,
+    /// The time white has left (`WL` and `OW`).
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub white_time: Option<TimeLeft>,
+}
+
+impl Node {
+    /// Creates a node for a move or pass.
+    pub(crate) fn with_play(play: Play) -> Node {
+        Node { play: Some(play), ..Default::default() }
+    }
+
+    /// Creates a node for a move that breaks the rules, which is kept in the record without
+    /// changing the board.
+    pub(crate) fn with_illegal_move(m: Move) -> Node {
+        Node { play: Some(Play::Move(m)), illegal: true, ..Default::default() }
+    }
+
+    /// Returns the move or pass played at this node, if any.
+    pub fn play(&self) -> Option<Play> {
+        self.play
+    }
+
+    /// Returns whether the move at this node is illegal, in which case it was read from a game
+    /// record as it was written but left the board unchanged.
+    pub fn is_illegal(&self) -> bool {
+        self.illegal
+    }
+
+    /// Returns the stone played on the board at this node, if any (not a pass or illegal move).
+    pub(crate) fn played(&self) -> Option<Move> {
+        match self.play {
+            Some(Play::Move(m)) if !self.illegal => Some(m),
+            _ => None,
+        }
+    }
+
+    /// Returns the stones placed (or removed, as `Stone::Empty`) at this node without playing a
+    /// move. The setup of the root node is the initial position, so it is always empty.
+    pub fn setup(&self) -> &[(Point, Stone)] {
+        &self.setup
+    }
+
+    /// Records a stone placed (or removed) at this node, replacing any earlier change to the
+    /// same point.
+    pub(crate) fn add_setup(&mut self, point: Point, stone: Stone) {
+        self.setup.retain(|&(p, _)| p != point);
+        self.setup.push((point, stone));
+    }
+
+    /// Reads a property of the node, returning false if it isn't one that a `Node` describes.
+    pub(crate) fn read_property(&mut self, property: &Property, size: usize) -> bool {
+        let values = &property.values;
+        let first = values.first().map(String::as_str).unwrap_or("");
+        let point = |value: &str| Point::from_sgf(value, size);
+        let pair = |value: &str| {
+            let mut parts = value.splitn(2, ':');
+            Some((point(parts.next()?)?, point(parts.next()?)?))
+        };
+
+        match property.ident.as_str() {
+            "C" => self.comment = Some(first.to_string()),
+            "N" => self.name = Some(first.to_string()),
+            "DD" => self.dimmed.extend(sgf::parse_points(values, size)),
+            "AR" => self.arrows.extend(values.iter().filter_map(|v| pair(v))),
+            "LN" => self.lines.extend(values.iter().filter_map(|v| pair(v))),
+            "LB" => {
+                for value in values {
+                    let mut parts = value.splitn(2, ':');
+
+                    if let (Some(p), Some(label)) = (parts.next().and_then(point), parts.next()) {
+                        self.markup.push((p, Markup::Label(label.to_string())));
+                    }
+                }
+            },
+            "BM" => self.annotations.push(Annotation::BadMove(Emphasis::from_sgf(first))),
+            "TE" => self.annotations.push(Annotation::Tesuji(Emphasis::from_sgf(first))),
+            "DO" => self.annotations.push(Annotation::Doubtful),
+            "IT" => self.annotations.push(Annotation::Interesting),
+            "GB" => self.annotations.push(Annotation::GoodForBlack(Emphasis::from_sgf(first))),
+            "GW" => self.annotations.push(Annotation::GoodForWhite(Emphasis::from_sgf(first))),
+            "DM" => self.annotations.push(Annotation::Even(Emphasis::from_sgf(first))),
+            "UC" => self.annotations.push(Annotation::Unclear(Emphasis::from_sgf(first))),
+            "HO" => self.annotations.push(Annotation::Hotspot(Emphasis::from_sgf(first))),
+            "V" => {
+                if let Ok(value) = first.trim().parse() {
+                    self.annotations.push(Annotation::Value(value));
+                }
+            },
+            "BL" | "WL" | "OB" | "OW" => {
+                let time_left = match property.ident.as_str() {
+                    "BL" | "OB" => &mut self.black_time,
+                    _ => &mut self.white_time,
+                };
+
+                // Values that can't be read (or are out of range for a duration) are dropped.
+                if property.ident.ends_with('L') {
+                    if let Some(time) = first.trim().parse().ok().and_then(|t| Duration::try_from_secs_f64(t).ok()) {
+                        time_left.get_or_insert(TimeLeft { time, periods: None }).time = time;
+                    }
+                } else if let Ok(periods) = first.trim().parse() {
+                    time_left.get_or_insert(TimeLeft { time: Duration::default(), periods: None }).periods = Some(periods);
+                }
+            },
+            ident => match MARKUP_PROPERTIES.iter().find(|&&(i, _)| i == ident) {
+                Some((_, markup)) => {
+                    self.markup.extend(sgf::parse_points(values, size).into_iter().map(|p| (p, markup.clone())));
+                },
+                None => return false,
+            },
+        }
+
+        true
+    }
+
+    /// Writes the properties of the node (without the leading semicolon).
+    pub(crate) fn write_sgf(&self, sgf: &mut String, options: &SgfOptions) {
+        if let Some(play) = self.play {
+            let ident = if play.stone() == Stone::Black { "B" } else { "W" };
+            let value = match play {
+                Play::Move(m) => m.point.to_sgf(),
+                Play::Pass(_) => String::new(),
+            };
+
+            sgf::write_property(sgf, ident, &[value]);
+        }
+
+        for &(ident, stone) in &[("AB", Stone::Black), ("AW", Stone::White), ("AE", Stone::Empty)] {
+            let points: Vec<Point> = self.setup.iter()
+                .filter(|&&(_, s)| s == stone)
+                .map(|&(p, _)| p)
+                .collect();
+
+            sgf::write_property(sgf, ident, &sgf::format_points(&points, options));
+        }
+
+        for &(time, periods, time_left) in &[("BL", "OB", &self.black_time), ("WL", "OW", &self.white_time)] {
+            if let Some(time_left) = *time_left {
+                sgf::write_property(sgf, time, &[time_left.time.as_secs_f64().to_string()]);
+
+                if let Some(n) = time_left.periods {
+                    sgf::write_property(sgf, periods, &[n.to_string()]);
+                }
+            }
+        }
+
+        for annotation in &self.annotations {
+            let (ident, value) = annotation.to_sgf();
+            sgf::write_property(sgf, ident, &[value]);
+        }
+
+        if let Some(ref name) = self.name {
+            sgf::write_property(sgf, "N", &[name]);
+        }
+
+        if let Some(ref comment) = self.comment {
+            sgf::write_property(sgf, "C", &[comment]);
+        }
+
+        for &(ident, ref kind) in MARKUP_PROPERTIES {
+            let points: Vec<Point> = self.markup.iter()
+                .filter(|&(_, m)| m == kind)
+                .map(|&(p, _)| p)
+                .collect();
+
+            sgf::write_property(sgf, ident, &sgf::format_points(&points, options));
+        }
+
+        let labels: Vec<String> = self.markup.iter()
+            .filter_map(|(p, m)| match *m {
+                Markup::Label(ref label) => Some(format!("{}:{}", p.to_sgf(), label)),
+                _ => None,
+            })
+            .collect();
+
+        sgf::write_property(sgf, "LB", &labels);
+
+        let pair = |&(a, b): &(Point, Point)| format!("{}:{}", a.to_sgf(), b.to_sgf());
+
+        sgf::write_property(sgf, "DD", &sgf::format_points(&self.dimmed, options));
+        sgf::write_property(sgf, "AR", &self.arrows.iter().map(pair).collect::<Vec<_>>());
+        sgf::write_property(sgf, "LN", &self.lines.iter().map(pair).collect::<Vec<_>>());
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Annotation, Emphasis, Node};
+    use clock::TimeLeft;
+    use markup::Markup;
+    use point::Point;
+    use sgf::{self, SgfOptions};
+
+    fn read(text: &str) -> Node {
+        let mut node = Node::default();
+
+        for property in &sgf::parse(text)[0].nodes[0] {
+            node.read_property(property, 19);
+        }
+
+        node
+    }
+
+    #[test]
+    fn comments_and_markup() {
+        let node = read("(;C[Nice move\\]]N[Key point]TR[aa][bb]CR[cc]SQ[dd]MA[ee]LB[ff:A][gg:12]\
+            DD[hh]AR[aa:bb]LN[cc:dd])");
+
+        assert_eq!(node.comment, Some("Nice move]".to_string()));
+        assert_eq!(node.name, Some("Key point".to_string()));
+        assert_eq!(node.markup, vec![
+            (Point::new(0, 0), Markup::Triangle),
+            (Point::new(1, 1), Markup::Triangle),
+            (Point::new(2, 2), Markup::Circle),
+            (Point::new(3, 3), Markup::Square),
+            (Point::new(4, 4), Markup::Cross),
+            (Point::new(5, 5), Markup::Label("A".to_string())),
+            (Point::new(6, 6), Markup::Label("12".to_string())),
+        ]);
+        assert_eq!(node.dimmed, vec![Point::new(7, 7)]);
+        assert_eq!(node.arrows, vec![(Point::new(0, 0), Point::new(1, 1))]);
+        assert_eq!(node.lines, vec![(Point::new(2, 2), Point::new(3, 3))]);
+    }
+
+    #[test]
+    fn annotations() {
+        let node = read("(;BM[2]TE[1]DO[]IT[]GB[1]GW[2]DM[1]UC[1]HO[2]V[-3.5])");
+
+        assert_eq!(node.annotations, vec![
+            Annotation::BadMove(Emphasis::Strong),
+            Annotation::Tesuji(Emphasis::Normal),
+            Annotation::Doubtful,
+            Annotation::Interesting,
+            Annotation::GoodForBlack(Emphasis::Normal),
+            Annotation::GoodForWhite(Emphasis::Strong),
+            Annotation::Even(Emphasis::Normal),
+            Annotation::Unclear(Emphasis::Normal),
+            Annotation::Hotspot(Emphasis::Strong),
+            Annotation::Value(-3.5),
+        ]);
+    }
+
+    #[test]
+    fn unknown_properties() {
+        let mut node = Node::default();
+        let properties = &sgf::parse("(;XX[1]B[aa])")[0].nodes[0];
+
+        assert!(!node.read_property(&properties[0], 19));
+        assert!(!node.read_property(&properties[1], 19));
+        assert_eq!(node, Node::default());
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "(;TE[2]V[1.5]N[Key point]C[Nice move\\]]CR[cc]TR[aa][bb]LB[ff:A]DD[hh]AR[aa:bb]LN[cc:dd])";
+        let node = read(text);
+
+        let mut sgf = String::new();
+        node.write_sgf(&mut sgf, &SgfOptions::default());
+
+        assert_eq!(format!("(;{})", sgf), text);
+    }
+
+    #[test]
+    fn time_left() {
+        let node = read("(;B[aa]BL[12.5]OB[3]WL[600])");
+
+        assert_eq!(node.black_time, Some(TimeLeft { time: Duration::from_millis(12500), periods: Some(3) }));
+        assert_eq!(node.white_time, Some(TimeLeft { time: Duration::from_secs(600), periods: None }));
+
+        let mut sgf = String::new();
+        node.write_sgf(&mut sgf, &SgfOptions::default());
+        assert_eq!(sgf, "BL[12.5]OB[3]WL[600]");
+
+        let node = read("(;BL[inf]WL[1e30]OB[x])");
+        assert_eq!(node.black_time, None);
+        assert_eq!(node.white_time, None);
+    }
+
+    #[test]
+    fn compressed_markup() {
+        let node = read("(;TR[aa:bb]DD[cc:dc]SQ[ee])");
+
+        assert_eq!(node.markup.len(), 5);
+        assert_eq!(node.dimmed, vec![Point::new(2, 2), Point::new(3, 2)]);
+
+        let mut sgf = String::new();
+        node.write_sgf(&mut sgf, &SgfOptions { compress_points: true });
+        assert_eq!(sgf, "SQ[ee]TR[aa:bb]DD[cc:dc]");
+    }
+}
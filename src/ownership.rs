@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use board::{Board, Stone};
+use point::Point;
+
+/// The influence of a living stone, before it spreads to the points around it.
+const STONE_INFLUENCE: i32 = 128;
+
+/// Number of dilations and erosions in Bouzy's 5/21 algorithm.
+const DILATIONS: usize = 5;
+const EROSIONS: usize = 21;
+
+/// Influence at which a point is considered to be certainly owned.
+const OWNED: f32 = 8.0;
+
+impl Board {
+    /// Estimates who owns each point, from -1 (certainly white's) to 1 (certainly black's), in
+    /// row-major order (the ownership of `(x, y)` is at index `y * size + x`).
+    ///
+    /// Dead stones (see `Board::dead_stones`) are removed, and the influence of the remaining
+    /// stones is spread with Bouzy's 5/21 algorithm: five dilations, which spread influence to
+    /// points that aren't next to the opponent's, followed by 21 erosions, which wear away
+    /// influence that is contested. Living stones own their own points.
+    pub fn ownership(&self) -> Vec<f32> {
+        self.ownership_with_dead(&self.dead_stones())
+    }
+
+    /// Estimates ownership as `Board::ownership` does, given the stones that are dead.
+    pub(crate) fn ownership_with_dead(&self, dead: &HashSet<Point>) -> Vec<f32> {
+        let mut influence: Vec<i32> = self.points()
+            .map(|p| match self[p] {
+                _ if dead.contains(&p) => 0,
+                Stone::Empty => 0,
+                Stone::Black => STONE_INFLUENCE,
+                Stone::White => -STONE_INFLUENCE,
+            })
+            .collect();
+
+        for _ in 0..DILATIONS {
+            influence = self.dilate(&influence);
+        }
+
+        for _ in 0..EROSIONS {
+            influence = self.erode(&influence);
+        }
+
+        self.points().zip(influence)
+            .map(|(p, value)| match self[p] {
+                Stone::Black if !dead.contains(&p) => 1.0,
+                Stone::White if !dead.contains(&p) => -1.0,
+                _ => (value as f32 / OWNED).clamp(-1.0, 1.0),
+            })
+            .collect()
+    }
+
+    /// Returns the influence at each neighbour of the point at `index`.
+    fn neighbour_influence<'a>(&'a self, influence: &'a [i32], index: usize) -> impl Iterator<Item = i32> + 'a {
+        let point = (index % self.size, index / self.size);
+        self.neighbours(point).into_iter().map(move |p| influence[p.y * self.size + p.x])
+    }
+
+    /// Spreads influence to each point that isn't next to any of the opponent's influence.
+    fn dilate(&self, influence: &[i32]) -> Vec<i32> {
+        (0..influence.len())
+            .map(|i| {
+                let value = influence[i];
+                let neighbours: Vec<i32> = self.neighbour_influence(influence, i).collect();
+
+                if value >= 0 && neighbours.iter().all(|&n| n >= 0) {
+                    value + neighbours.iter().filter(|&&n| n > 0).count() as i32
+                } else if value <= 0 && neighbours.iter().all(|&n| n <= 0) {
+                    value - neighbours.iter().filter(|&&n| n < 0).count() as i32
+                } else {
+                    value
+                }
+            })
+            .collect()
+    }
+
+    /// Wears away influence at each point by the number of neighbours that don't share it.
+    fn erode(&self, influence: &[i32]) -> Vec<i32> {
+        (0..influence.len())
+            .map(|i| {
+                let value = influence[i];
+                let neighbours = self.neighbour_influence(influence, i);
+
+                if value > 0 {
+                    (value - neighbours.filter(|&n| n <= 0).count() as i32).max(0)
+                } else if value < 0 {
+                    (value + neighbours.filter(|&n| n >= 0).count() as i32).min(0)
+                } else {
+                    0
+                }
+            })
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use board::Board;
+
+    #[test]
+    fn empty_board() {
+        assert!(Board::with_size(9).ownership().iter().all(|&o| o == 0.0));
+    }
+
+    #[test]
+    fn territory() {
+        let board = Board::from_str("\
+            ..#O... \
+            .O#O... \
+            ..#O.#. \
+            ..#O... \
+            ..#O... \
+            ..#O... \
+            ..#O...");
+
+        let ownership = board.ownership();
+        let at = |x: usize, y: usize| ownership[y * board.size + x];
+
+        assert_eq!(ownership.len(), 49);
+
+        // Living stones own their own points, and dead stones are owned by the opponent.
+        assert_eq!(at(2, 0), 1.0);
+        assert_eq!(at(3, 0), -1.0);
+        assert_eq!(at(1, 1), 1.0);
+        assert_eq!(at(5, 2), -1.0);
+
+        for y in 0..7 {
+            assert!(at(0, y) > 0.5 && at(1, y) > 0.5);
+            assert!(at(4, y) < -0.5 && at(6, y) < -0.5);
+        }
+    }
+
+    #[test]
+    fn contested_points() {
+        let board = Board::from_str("\
+            ......... \
+            ......... \
+            ......... \
+            ......... \
+            ...#.O... \
+            ......... \
+            ......... \
+            ......... \
+            .........");
+
+        let ownership = board.ownership();
+
+        // The point between the stones is next to both, so neither owns it.
+        assert_eq!(ownership[4 * 9 + 4], 0.0);
+        assert!(ownership.iter().all(|&o| (-1.0..=1.0).contains(&o)));
+    }
+}
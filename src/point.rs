@@ -0,0 +1,203 @@
+use std::fmt;
+
+/// Letters used for GTP column labels (`I` is skipped to avoid confusion with `J`).
+const GTP_COLUMNS: &str = "ABCDEFGHJKLMNOPQRSTUVWXYZ";
+
+/// Letters used for SGF coordinates, in order of increasing index.
+const SGF_LETTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// An intersection on the board. `(0, 0)` is the top-left corner, with `x` increasing to the
+/// right and `y` increasing downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    /// Creates a new `Point` at `(x, y)`.
+    pub fn new(x: usize, y: usize) -> Point {
+        Point { x, y }
+    }
+
+    /// Returns true if the point lies on a board of the given `size`.
+    pub fn in_bounds(&self, size: usize) -> bool {
+        self.x < size && self.y < size
+    }
+
+    /// Parses SGF coordinates, returning `None` if they are malformed or off the board.
+    /// Ex. "ab" => (0, 1); "pd" => (15, 3)
+    pub fn from_sgf(sgf: &str, size: usize) -> Option<Point> {
+        let mut chars = sgf.chars();
+
+        let x = chars.next().and_then(|c| SGF_LETTERS.find(c))?;
+        let y = chars.next().and_then(|c| SGF_LETTERS.find(c))?;
+
+        if chars.next().is_some() {
+            return None;
+        }
+
+        Some(Point::new(x, y)).filter(|p| p.in_bounds(size))
+    }
+
+    /// Returns the SGF coordinates of the point.
+    pub fn to_sgf(&self) -> String {
+        let letter = |i: usize| SGF_LETTERS.chars().nth(i).expect("coordinate too large for SGF");
+        format!("{}{}", letter(self.x), letter(self.y))
+    }
+
+    /// Parses GTP vertex notation (case-insensitive), where rows are numbered upwards from the
+    /// bottom edge. Ex. "A19" => (0, 0); "Q16" => (15, 3) on a 19x19 board
+    pub fn from_gtp(gtp: &str, size: usize) -> Option<Point> {
+        let mut chars = gtp.chars();
+
+        let column = chars.next()?.to_ascii_uppercase();
+        let x = GTP_COLUMNS.find(column)?;
+        let row: usize = chars.as_str().parse().ok()?;
+
+        if row == 0 || row > size {
+            return None;
+        }
+
+        Some(Point::new(x, size - row)).filter(|p| p.in_bounds(size))
+    }
+
+    /// Returns the GTP vertex of the point on a board of the given `size`, or `None` if the
+    /// point is off the board or beyond the 25 columns GTP has letters for.
+    pub fn to_gtp(&self, size: usize) -> Option<String> {
+        let column = GTP_COLUMNS.chars().nth(self.x).filter(|_| self.in_bounds(size))?;
+        Some(format!("{}{}", column, size - self.y))
+    }
+
+    /// Parses Japanese-style numeric notation, where columns are counted from the right edge
+    /// and rows from the top edge, both starting from 1. Ex. "4-4" => (15, 3) on a 19x19 board
+    pub fn from_numeric(numeric: &str, size: usize) -> Option<Point> {
+        let mut parts = numeric.split('-');
+
+        let column: usize = parts.next()?.trim().parse().ok()?;
+        let row: usize = parts.next()?.trim().parse().ok()?;
+
+        if parts.next().is_some() || column == 0 || row == 0 || column > size {
+            return None;
+        }
+
+        Some(Point::new(size - column, row - 1)).filter(|p| p.in_bounds(size))
+    }
+
+    /// Returns the Japanese-style numeric notation of the point on a board of the given `size`,
+    /// or `None` if the point is off the board.
+    pub fn to_numeric(&self, size: usize) -> Option<String> {
+        Some(format!("{}-{}", size - self.x, self.y + 1)).filter(|_| self.in_bounds(size))
+    }
+}
+
+/// Returns the label of column `x` on a board of the given `size`: its GTP letter, or its SGF
+/// letter on boards too large for GTP. Returns `None` for columns beyond the SGF letters.
+pub(crate) fn column_label(x: usize, size: usize) -> Option<char> {
+    let letters = if size <= GTP_COLUMNS.len() { GTP_COLUMNS } else { SGF_LETTERS };
+    letters.chars().nth(x)
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Point {
+        Point::new(x, y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{column_label, Point};
+
+    #[test]
+    fn sgf_coordinates() {
+        assert_eq!(Point::from_sgf("aa", 19), Some(Point::new(0, 0)));
+        assert_eq!(Point::from_sgf("pd", 19), Some(Point::new(15, 3)));
+        assert_eq!(Point::from_sgf("sa", 19), Some(Point::new(18, 0)));
+        assert_eq!(Point::from_sgf("Aa", 52), Some(Point::new(26, 0)));
+        assert_eq!(Point::new(15, 3).to_sgf(), "pd");
+        assert_eq!(Point::new(26, 51).to_sgf(), "AZ");
+    }
+
+    #[test]
+    fn invalid_sgf_coordinates() {
+        assert_eq!(Point::from_sgf("tt", 19), None);
+        assert_eq!(Point::from_sgf("", 19), None);
+        assert_eq!(Point::from_sgf("a", 19), None);
+        assert_eq!(Point::from_sgf("aa:cc", 19), None);
+        assert_eq!(Point::from_sgf("a1", 19), None);
+        assert_eq!(Point::from_sgf("jj", 9), None);
+    }
+
+    #[test]
+    fn gtp_coordinates() {
+        assert_eq!(Point::from_gtp("A19", 19), Some(Point::new(0, 0)));
+        assert_eq!(Point::from_gtp("Q16", 19), Some(Point::new(15, 3)));
+        assert_eq!(Point::from_gtp("q16", 19), Some(Point::new(15, 3)));
+        assert_eq!(Point::from_gtp("J1", 19), Some(Point::new(8, 18)));
+        assert_eq!(Point::from_gtp("T1", 19), Some(Point::new(18, 18)));
+        assert_eq!(Point::new(8, 0).to_gtp(19), Some("J19".to_string()));
+        assert_eq!(Point::new(4, 4).to_gtp(9), Some("E5".to_string()));
+    }
+
+    #[test]
+    fn invalid_gtp_coordinates() {
+        assert_eq!(Point::from_gtp("I5", 19), None);
+        assert_eq!(Point::from_gtp("A0", 19), None);
+        assert_eq!(Point::from_gtp("A20", 19), None);
+        assert_eq!(Point::from_gtp("K5", 9), None);
+        assert_eq!(Point::from_gtp("pass", 19), None);
+        assert_eq!(Point::from_gtp("", 19), None);
+        assert_eq!(Point::new(9, 0).to_gtp(9), None);
+        assert_eq!(Point::new(0, 9).to_gtp(9), None);
+        assert_eq!(Point::new(25, 0).to_gtp(30), None);
+    }
+
+    #[test]
+    fn column_labels() {
+        assert_eq!(column_label(8, 19), Some('J'));
+        assert_eq!(column_label(24, 25), Some('Z'));
+        assert_eq!(column_label(8, 30), Some('i'));
+        assert_eq!(column_label(29, 30), Some('D'));
+        assert_eq!(column_label(52, 60), None);
+    }
+
+    #[test]
+    fn numeric_coordinates() {
+        assert_eq!(Point::from_numeric("4-4", 19), Some(Point::new(15, 3)));
+        assert_eq!(Point::from_numeric("1-1", 19), Some(Point::new(18, 0)));
+        assert_eq!(Point::from_numeric("19-19", 19), Some(Point::new(0, 18)));
+        assert_eq!(Point::new(15, 3).to_numeric(19), Some("4-4".to_string()));
+        assert_eq!(Point::new(2, 6).to_numeric(9), Some("7-7".to_string()));
+    }
+
+    #[test]
+    fn invalid_numeric_coordinates() {
+        assert_eq!(Point::from_numeric("0-4", 19), None);
+        assert_eq!(Point::from_numeric("4-20", 19), None);
+        assert_eq!(Point::from_numeric("10-1", 9), None);
+        assert_eq!(Point::from_numeric("4-4-4", 19), None);
+        assert_eq!(Point::from_numeric("4", 19), None);
+        assert_eq!(Point::new(19, 0).to_numeric(19), None);
+        assert_eq!(Point::new(0, 19).to_numeric(19), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        for y in 0..19 {
+            for x in 0..19 {
+                let point = Point::new(x, y);
+                assert_eq!(Point::from_sgf(&point.to_sgf(), 19), Some(point));
+                assert_eq!(point.to_gtp(19).and_then(|gtp| Point::from_gtp(&gtp, 19)), Some(point));
+                assert_eq!(point.to_numeric(19).and_then(|n| Point::from_numeric(&n, 19)), Some(point));
+            }
+        }
+    }
+}
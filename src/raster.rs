@@ -0,0 +1,460 @@
+#[cfg(feature = "gif")]
+use std::collections::HashMap;
+
+use board::{Board, Stone};
+#[cfg(feature = "gif")]
+use game::Game;
+use markup::Markup;
+use point::Point;
+
+type Color = [u8; 3];
+
+const BOARD_COLOR: Color = [0xdc, 0xb3, 0x5c];
+const BLACK: Color = [0x00, 0x00, 0x00];
+const WHITE: Color = [0xff, 0xff, 0xff];
+
+/// Number of distinct levels that anti-aliased edges are quantized to, which keeps the number of
+/// colors in an image small enough for a GIF palette.
+const COVERAGE_LEVELS: f64 = 16.0;
+
+/// Options controlling how `Board::to_image` rasterizes a board.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RasterOptions {
+    /// Distance between adjacent grid lines, in pixels.
+    pub spacing: u32,
+    /// Point to mark as the most recently played move.
+    pub last_move: Option<Point>,
+    /// Markup to draw on top of points. Labels aren't drawn, since there is no font to draw them.
+    pub markup: Vec<(Point, Markup)>,
+    /// The top-left and bottom-right corners (inclusive) of the part of the board to draw, or
+    /// `None` to draw the whole board.
+    pub region: Option<(Point, Point)>,
+    /// Time to show each move for when animating a game, in hundredths of a second.
+    pub frame_delay: u16,
+}
+
+impl Default for RasterOptions {
+    fn default() -> RasterOptions {
+        RasterOptions {
+            spacing: 24,
+            last_move: None,
+            markup: Vec::new(),
+            region: None,
+            frame_delay: 100,
+        }
+    }
+}
+
+/// An RGB image, stored row by row with three bytes per pixel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    fn new(width: u32, height: u32, color: Color) -> Image {
+        let pixels = color.iter().cloned().cycle().take((width * height * 3) as usize).collect();
+        Image { width, height, pixels }
+    }
+
+    /// Returns the color of the pixel at `(x, y)`.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        let i = ((y * self.width + x) * 3) as usize;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]]
+    }
+
+    /// Blends `color` into the pixel at `(x, y)`, where `coverage` is the fraction of the pixel
+    /// covered by the shape being drawn.
+    fn blend(&mut self, x: i64, y: i64, color: Color, coverage: f64) {
+        if x < 0 || y < 0 || x >= i64::from(self.width) || y >= i64::from(self.height) {
+            return;
+        }
+
+        let alpha = (coverage.clamp(0.0, 1.0) * COVERAGE_LEVELS).round() / COVERAGE_LEVELS;
+        if alpha == 0.0 {
+            return;
+        }
+
+        let i = ((y as u32 * self.width + x as u32) * 3) as usize;
+        for (old, &new) in self.pixels[i..i + 3].iter_mut().zip(color.iter()) {
+            *old = (f64::from(*old) + (f64::from(new) - f64::from(*old)) * alpha).round() as u8;
+        }
+    }
+
+    /// Fills the pixels covered by a shape, given the signed distance from a pixel's center to the
+    /// shape's edge (negative inside) and the bounding box of the shape.
+    fn fill<F: Fn(f64, f64) -> f64>(&mut self, bounds: (f64, f64, f64, f64), color: Color, distance: F) {
+        let (x0, y0, x1, y1) = bounds;
+
+        for y in (y0.floor() as i64 - 1)..=(y1.ceil() as i64 + 1) {
+            for x in (x0.floor() as i64 - 1)..=(x1.ceil() as i64 + 1) {
+                let d = distance(x as f64 + 0.5, y as f64 + 0.5);
+                self.blend(x, y, color, 0.5 - d);
+            }
+        }
+    }
+
+    fn fill_rect(&mut self, x0: f64, y0: f64, x1: f64, y1: f64, color: Color) {
+        self.fill((x0, y0, x1, y1), color, |x, y| {
+            (x0 - x).max(x - x1).max(y0 - y).max(y - y1)
+        });
+    }
+
+    fn fill_circle(&mut self, cx: f64, cy: f64, r: f64, color: Color) {
+        self.fill((cx - r, cy - r, cx + r, cy + r), color, |x, y| {
+            (x - cx).hypot(y - cy) - r
+        });
+    }
+
+    fn stroke_circle(&mut self, cx: f64, cy: f64, r: f64, width: f64, color: Color) {
+        self.fill((cx - r - width, cy - r - width, cx + r + width, cy + r + width), color, |x, y| {
+            ((x - cx).hypot(y - cy) - r).abs() - width / 2.0
+        });
+    }
+
+    fn stroke_line(&mut self, from: (f64, f64), to: (f64, f64), width: f64, color: Color) {
+        let ((ax, ay), (bx, by)) = (from, to);
+        let bounds = (ax.min(bx) - width, ay.min(by) - width, ax.max(bx) + width, ay.max(by) + width);
+
+        self.fill(bounds, color, |x, y| {
+            // Distance from `(x, y)` to the closest point on the segment.
+            let (dx, dy) = (bx - ax, by - ay);
+            let t = (((x - ax) * dx + (y - ay) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+            (x - ax - t * dx).hypot(y - ay - t * dy) - width / 2.0
+        });
+    }
+
+    fn stroke_polygon(&mut self, vertices: &[(f64, f64)], width: f64, color: Color) {
+        for (i, &from) in vertices.iter().enumerate() {
+            let to = vertices[(i + 1) % vertices.len()];
+            self.stroke_line(from, to, width, color);
+        }
+    }
+}
+
+impl Board {
+    /// Rasterizes the board into an RGB image.
+    pub fn to_image(&self, options: &RasterOptions) -> Image {
+        // A board without any points is drawn as just its margin.
+        if self.size == 0 {
+            return Image::new(2 * options.spacing, 2 * options.spacing, BOARD_COLOR);
+        }
+
+        let last = self.size - 1;
+        let (min, max) = match options.region {
+            Some((a, b)) => (
+                Point::new(a.x.min(b.x).min(last), a.y.min(b.y).min(last)),
+                Point::new(a.x.max(b.x).min(last), a.y.max(b.y).min(last)),
+            ),
+            None => (Point::new(0, 0), Point::new(last, last)),
+        };
+
+        let s = f64::from(options.spacing);
+        let width = (max.x - min.x + 2) as u32 * options.spacing;
+        let height = (max.y - min.y + 2) as u32 * options.spacing;
+        let mut image = Image::new(width, height, BOARD_COLOR);
+
+        // Centers of points are offset by half a pixel so that one pixel wide grid lines are
+        // drawn crisply.
+        let px = |x: usize| (x - min.x + 1) as f64 * s + 0.5;
+        let py = |y: usize| (y - min.y + 1) as f64 * s + 0.5;
+        let extend = |cropped: bool| if cropped { s / 2.0 } else { 0.0 };
+
+        for y in min.y..=max.y {
+            let (x0, x1) = (px(min.x) - extend(min.x > 0), px(max.x) + extend(max.x < last));
+            image.fill_rect(x0 - 0.5, py(y) - 0.5, x1 + 0.5, py(y) + 0.5, BLACK);
+        }
+
+        for x in min.x..=max.x {
+            let (y0, y1) = (py(min.y) - extend(min.y > 0), py(max.y) + extend(max.y < last));
+            image.fill_rect(px(x) - 0.5, y0 - 0.5, px(x) + 0.5, y1 + 0.5, BLACK);
+        }
+
+        let in_region = |p: &Point| (min.x..=max.x).contains(&p.x) && (min.y..=max.y).contains(&p.y);
+
+        for point in self.points().filter(&in_region) {
+            let (cx, cy) = (px(point.x), py(point.y));
+
+            match self[point] {
+                Stone::Empty if self.star_point(point) => image.fill_circle(cx, cy, s * 0.1, BLACK),
+                Stone::Empty => {},
+                Stone::Black => image.fill_circle(cx, cy, s * 0.475, BLACK),
+                Stone::White => {
+                    image.fill_circle(cx, cy, s * 0.475, BLACK);
+                    image.fill_circle(cx, cy, s * 0.475 - 1.0, WHITE);
+                },
+            }
+        }
+
+        let contrast = |point: Point| if self[point] == Stone::Black { WHITE } else { BLACK };
+
+        if let Some(point) = options.last_move.filter(&in_region) {
+            image.stroke_circle(px(point.x), py(point.y), s * 0.25, 2.0, contrast(point));
+        }
+
+        for &(point, ref markup) in options.markup.iter().filter(|(p, _)| in_region(p)) {
+            let (cx, cy, r) = (px(point.x), py(point.y), s * 0.25);
+            let color = contrast(point);
+
+            if self[point] == Stone::Empty {
+                // Hide the grid lines underneath the markup so that it is legible.
+                image.fill_circle(cx, cy, s * 0.4, BOARD_COLOR);
+            }
+
+            match *markup {
+                Markup::Circle => image.stroke_circle(cx, cy, r, 2.0, color),
+                Markup::Cross => {
+                    image.stroke_line((cx - r, cy - r), (cx + r, cy + r), 2.0, color);
+                    image.stroke_line((cx - r, cy + r), (cx + r, cy - r), 2.0, color);
+                },
+                Markup::Square => {
+                    let corners = [(cx - r, cy - r), (cx + r, cy - r), (cx + r, cy + r), (cx - r, cy + r)];
+                    image.stroke_polygon(&corners, 2.0, color);
+                },
+                Markup::Triangle => {
+                    let corners = [(cx, cy - r), (cx + r * 0.866, cy + r * 0.5), (cx - r * 0.866, cy + r * 0.5)];
+                    image.stroke_polygon(&corners, 2.0, color);
+                },
+                Markup::Label(_) => {},
+            }
+        }
+
+        image
+    }
+
+    /// Rasterizes the board into a PNG image.
+    #[cfg(feature = "png")]
+    pub fn to_png(&self, options: &RasterOptions) -> Vec<u8> {
+        let image = self.to_image(options);
+        let mut png = Vec::new();
+
+        {
+            let mut encoder = ::png::Encoder::new(&mut png, image.width, image.height);
+            encoder.set_color(::png::ColorType::Rgb);
+            encoder.set_depth(::png::BitDepth::Eight);
+
+            let mut writer = encoder.write_header().expect("failed to write PNG header");
+            writer.write_image_data(&image.pixels).expect("failed to write PNG data");
+        }
+
+        png
+    }
+}
+
+#[cfg(feature = "gif")]
+impl Game {
+    /// Renders an animated GIF stepping through every move of the game, starting from the
+    /// initial position. The most recent move is marked in each frame.
+    pub fn to_gif(&self, options: &RasterOptions) -> Vec<u8> {
+        let boards = self.history().iter().chain(Some(self.board()));
+        let last_moves = Some(None).into_iter().chain(self.moves().iter().map(|m| Some(m.point)));
+
+        let frames: Vec<Image> = boards.zip(last_moves)
+            .map(|(board, last_move)| board.to_image(&RasterOptions { last_move, ..options.clone() }))
+            .collect();
+
+        let (colors, indexed) = index_colors(&frames);
+        let (width, height) = (frames[0].width as u16, frames[0].height as u16);
+        let mut gif = Vec::new();
+
+        {
+            let mut encoder = ::gif::Encoder::new(&mut gif, width, height, &colors)
+                .expect("failed to write GIF header");
+            encoder.set_repeat(::gif::Repeat::Infinite).expect("failed to write GIF header");
+
+            for pixels in indexed {
+                let mut frame = ::gif::Frame::from_indexed_pixels(width, height, pixels, None);
+                frame.delay = options.frame_delay;
+                encoder.write_frame(&frame).expect("failed to write GIF frame");
+            }
+        }
+
+        gif
+    }
+}
+
+/// Builds a single global palette for `frames`, returning it along with the palette index of
+/// each pixel in each frame.
+///
+/// Anti-aliased edges are quantized, so frames normally only use a handful of distinct colors.
+/// Once the palette's 256 entries are used up, any further colors are mapped to the nearest
+/// color already in it.
+#[cfg(feature = "gif")]
+fn index_colors(frames: &[Image]) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let mut palette: HashMap<Color, u8> = HashMap::new();
+    let mut colors: Vec<u8> = Vec::new();
+
+    let indexed = frames.iter()
+        .map(|frame| {
+            frame.pixels.chunks(3).map(|p| {
+                let color = [p[0], p[1], p[2]];
+
+                *palette.entry(color).or_insert_with(|| {
+                    if colors.len() < 256 * 3 {
+                        colors.extend_from_slice(&color);
+                        return (colors.len() / 3 - 1) as u8;
+                    }
+
+                    let distance = |c: &[u8]| -> u32 {
+                        c.iter().zip(&color).map(|(&a, &b)| (a as i32 - b as i32).pow(2) as u32).sum()
+                    };
+
+                    colors.chunks(3).enumerate()
+                        .min_by_key(|&(_, c)| distance(c))
+                        .map(|(i, _)| i as u8)
+                        .expect("palette is empty")
+                })
+            }).collect()
+        })
+        .collect();
+
+    (colors, indexed)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{RasterOptions, BLACK, BOARD_COLOR, WHITE};
+    use board::{Board, Stone};
+    use markup::Markup;
+    use point::Point;
+
+    #[test]
+    fn empty_board() {
+        let image = Board::with_size(9).to_image(&RasterOptions::default());
+
+        assert_eq!((image.width, image.height), (240, 240));
+        assert_eq!(image.pixels.len(), 240 * 240 * 3);
+        assert_eq!(image.pixel(0, 0), BOARD_COLOR);
+        assert_eq!(image.pixel(24, 24), BLACK);
+        assert_eq!(image.pixel(30, 24), BLACK);
+        assert_eq!(image.pixel(30, 30), BOARD_COLOR);
+
+        // Star point at the center of the board.
+        assert_eq!(image.pixel(121, 121), BLACK);
+        assert_eq!(image.pixel(97, 97), BOARD_COLOR);
+    }
+
+    #[test]
+    fn empty_size() {
+        let image = Board::with_size(0).to_image(&RasterOptions::default());
+
+        assert_eq!((image.width, image.height), (48, 48));
+        assert_eq!(image.pixel(24, 24), BOARD_COLOR);
+    }
+
+    #[test]
+    fn stones() {
+        let board = Board::from_str("\
+            #O. \
+            ... \
+            ...");
+
+        let image = board.to_image(&RasterOptions::default());
+
+        assert_eq!(image.pixel(30, 30), BLACK);
+        assert_eq!(image.pixel(54, 30), WHITE);
+        assert_eq!(image.pixel(78, 30), BOARD_COLOR);
+    }
+
+    #[test]
+    fn last_move_marker() {
+        let mut board = Board::with_size(3);
+        board[(0, 0)] = Stone::Black;
+
+        let options = RasterOptions { last_move: Some(Point::new(0, 0)), ..Default::default() };
+        let image = board.to_image(&options);
+
+        assert_eq!(image.pixel(30, 24), WHITE);
+        assert_eq!(image.pixel(27, 24), BLACK);
+    }
+
+    #[test]
+    fn markup() {
+        let board = Board::with_size(3);
+        let options = RasterOptions {
+            markup: vec![(Point::new(1, 1), Markup::Square)],
+            ..Default::default()
+        };
+
+        let image = board.to_image(&options);
+
+        // Grid lines are hidden underneath the markup.
+        assert_eq!(image.pixel(48, 48), BOARD_COLOR);
+        assert_eq!(image.pixel(42, 48), BLACK);
+    }
+
+    #[test]
+    fn cropped_region() {
+        let options = RasterOptions {
+            region: Some((Point::new(0, 0), Point::new(4, 2))),
+            ..Default::default()
+        };
+
+        let image = Board::with_size(9).to_image(&options);
+
+        // Lines are extended halfway to the next line to the right and bottom.
+        assert_eq!((image.width, image.height), (144, 96));
+        assert_eq!(image.pixel(132, 24), BLACK);
+        assert_eq!(image.pixel(138, 24), BOARD_COLOR);
+        assert_eq!(image.pixel(24, 84), BLACK);
+        assert_eq!(image.pixel(24, 90), BOARD_COLOR);
+        assert_eq!(image.pixel(12, 24), BOARD_COLOR);
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn png() {
+        let png = Board::with_size(9).to_png(&RasterOptions::default());
+        let decoder = ::png::Decoder::new(&png[..]).read_info().unwrap();
+
+        assert_eq!(decoder.info().width, 240);
+        assert_eq!(decoder.info().height, 240);
+    }
+
+    #[test]
+    #[cfg(feature = "gif")]
+    fn gif() {
+        use game::Game;
+
+        let mut game = Game::from_str("\
+            ... \
+            ... \
+            ...");
+
+        assert!(game.make_move(Stone::Black, (1, 1)));
+        assert!(game.make_move(Stone::White, (0, 0)));
+
+        let gif = game.to_gif(&RasterOptions::default());
+        let mut decoder = ::gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        let mut frames = 0;
+
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 100);
+            frames += 1;
+        }
+
+        // One frame for the initial position, plus one per move.
+        assert_eq!(frames, 3);
+    }
+
+    #[test]
+    #[cfg(feature = "gif")]
+    fn gif_palette_overflow() {
+        use super::{index_colors, Image};
+
+        // 300 distinct colors, of which the last 44 have to share palette entries.
+        let pixels: Vec<u8> = (0..300u32).flat_map(|i| vec![(i % 256) as u8, (i / 256) as u8, 0]).collect();
+        let image = Image { width: 300, height: 1, pixels };
+
+        let (colors, indexed) = index_colors(&[image]);
+        assert_eq!(colors.len(), 256 * 3);
+        assert_eq!(indexed[0][255], 255);
+
+        // The color (0, 1, 0) is nearest to the palette entry for (0, 0, 0).
+        assert_eq!(indexed[0][256], 0);
+        assert_eq!(indexed[0][299], 43);
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use board::Stone;
+
+/// The result of a game, as recorded by the SGF `RE` property.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum GameResult {
+    /// The player won by the given number of points.
+    Score(Stone, f32),
+    /// The player won because their opponent resigned.
+    Resignation(Stone),
+    /// The player won because their opponent ran out of time.
+    Time(Stone),
+    /// The player won because their opponent forfeited.
+    Forfeit(Stone),
+    /// The player won, without the reason being recorded.
+    Win(Stone),
+    /// The game ended in a draw (jigo).
+    Draw,
+    /// The game was suspended or annulled without a result.
+    Void,
+    /// The result is unknown.
+    Unknown,
+}
+
+impl GameResult {
+    /// Parses an SGF result, such as `B+R`, `W+3.5`, `0` or `Void`. Returns `None` if the result
+    /// isn't understood.
+    pub fn from_sgf(text: &str) -> Option<GameResult> {
+        let text = text.trim();
+
+        match text.to_lowercase().as_str() {
+            "0" | "draw" | "jigo" => return Some(GameResult::Draw),
+            "void" => return Some(GameResult::Void),
+            "?" => return Some(GameResult::Unknown),
+            _ => {},
+        }
+
+        let mut parts = text.splitn(2, '+');
+        let winner = match parts.next()?.trim() {
+            "B" | "b" => Stone::Black,
+            "W" | "w" => Stone::White,
+            _ => return None,
+        };
+
+        let reason = parts.next()?.trim();
+
+        match reason.to_lowercase().as_str() {
+            "" => Some(GameResult::Win(winner)),
+            "r" | "resign" => Some(GameResult::Resignation(winner)),
+            "t" | "time" => Some(GameResult::Time(winner)),
+            "f" | "forfeit" => Some(GameResult::Forfeit(winner)),
+            score => score.parse().ok().map(|score| GameResult::Score(winner, score)),
+        }
+    }
+
+    /// Returns the winner of the game, if there is one.
+    pub fn winner(&self) -> Option<Stone> {
+        match *self {
+            GameResult::Score(stone, _) | GameResult::Resignation(stone) | GameResult::Time(stone)
+            | GameResult::Forfeit(stone) | GameResult::Win(stone) => Some(stone),
+            GameResult::Draw | GameResult::Void | GameResult::Unknown => None,
+        }
+    }
+}
+
+/// Formats the result as an SGF `RE` value.
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let winner = match self.winner() {
+            Some(Stone::White) => "W",
+            _ => "B",
+        };
+
+        match *self {
+            GameResult::Score(_, score) => write!(f, "{}+{}", winner, score),
+            GameResult::Resignation(_) => write!(f, "{}+R", winner),
+            GameResult::Time(_) => write!(f, "{}+T", winner),
+            GameResult::Forfeit(_) => write!(f, "{}+F", winner),
+            GameResult::Win(_) => write!(f, "{}+", winner),
+            GameResult::Draw => write!(f, "0"),
+            GameResult::Void => write!(f, "Void"),
+            GameResult::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::GameResult;
+    use board::Stone;
+
+    #[test]
+    fn parse_results() {
+        assert_eq!(GameResult::from_sgf("B+R"), Some(GameResult::Resignation(Stone::Black)));
+        assert_eq!(GameResult::from_sgf("W+Resign"), Some(GameResult::Resignation(Stone::White)));
+        assert_eq!(GameResult::from_sgf("W+T"), Some(GameResult::Time(Stone::White)));
+        assert_eq!(GameResult::from_sgf("B+F"), Some(GameResult::Forfeit(Stone::Black)));
+        assert_eq!(GameResult::from_sgf(" W+3.5 "), Some(GameResult::Score(Stone::White, 3.5)));
+        assert_eq!(GameResult::from_sgf("B+"), Some(GameResult::Win(Stone::Black)));
+        assert_eq!(GameResult::from_sgf("0"), Some(GameResult::Draw));
+        assert_eq!(GameResult::from_sgf("Void"), Some(GameResult::Void));
+        assert_eq!(GameResult::from_sgf("?"), Some(GameResult::Unknown));
+        assert_eq!(GameResult::from_sgf("Black won"), None);
+        assert_eq!(GameResult::from_sgf("B+lots"), None);
+    }
+
+    #[test]
+    fn format_results() {
+        for text in &["B+R", "W+T", "B+F", "W+0.5", "B+", "0", "Void", "?"] {
+            assert_eq!(GameResult::from_sgf(text).unwrap().to_string(), *text);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+
+use board::{Board, Chain, Stone};
+use point::Point;
+
+impl Board {
+    /// Returns the stones that are in seki: chains of both colors that live together because
+    /// they share liberties that neither side can fill without putting itself in atari.
+    ///
+    /// Each chain in seki has at least one liberty shared with an opposing chain, its other
+    /// liberties are in eyes of its own (empty regions bordered only by its color), and every
+    /// opposing chain it shares a liberty with is in seki too.
+    pub fn seki(&self) -> HashSet<Point> {
+        let mut chains: Vec<Chain> = self.chains()
+            .filter(|chain| chain.liberties().len() >= 2)
+            .filter(|chain| self.mutual_liberties(chain))
+            .collect();
+
+        // Chains are discarded until the ones that are left only share liberties with each other.
+        loop {
+            let count = chains.len();
+            let stones: HashSet<Point> = chains.iter().flat_map(|c| c.stones().iter().cloned()).collect();
+
+            chains.retain(|chain| {
+                chain.liberties().iter()
+                    .flat_map(|&p| self.neighbours(p))
+                    .filter(|&p| self[p] == chain.color().not())
+                    .all(|p| stones.contains(&p))
+            });
+
+            if chains.len() == count {
+                return stones;
+            }
+        }
+    }
+
+    /// Returns whether the stone at `point` is part of a chain in seki (see `Board::seki`). The
+    /// seki on the rest of the board are only worked out if the chain could be in one, so use
+    /// `Board::seki` instead to check many points.
+    pub fn in_seki<P: Into<Point>>(&self, point: P) -> bool {
+        let point = point.into();
+
+        match self.get(point).and_then(|_| self.chain(point)) {
+            Some(chain) => {
+                chain.liberties().len() >= 2 && self.mutual_liberties(&chain) && self.seki().contains(&point)
+            },
+            None => false,
+        }
+    }
+
+    /// Returns whether all of the liberties of `chain` are either its own eyes or shared with
+    /// the opponent in a way that neither side can approach, with at least one shared.
+    fn mutual_liberties(&self, chain: &Chain) -> bool {
+        let color = chain.color();
+        let mut shared = 0;
+
+        for &liberty in chain.liberties() {
+            let eye = self.region(liberty, |p| self[p] == Stone::Empty);
+
+            if self.border(&eye).iter().all(|p| self[*p] == color) {
+                continue;
+            }
+
+            if !self.neighbours(liberty).iter().any(|p| self[*p] == color.not()) {
+                return false;
+            }
+
+            for &stone in &[Stone::Black, Stone::White] {
+                if !self.self_atari(stone, liberty) {
+                    return false;
+                }
+            }
+
+            shared += 1;
+        }
+
+        shared > 0
+    }
+
+    /// Returns whether playing `stone` at the empty `point` is either suicide, or leaves the
+    /// stone's chain with at most one liberty. This is worked out from the chains around `point`
+    /// rather than by playing the move on a copy of the board.
+    fn self_atari(&self, stone: Stone, point: Point) -> bool {
+        let mut chain: HashSet<Point> = Some(point).into_iter().collect();
+        let mut captured = HashSet::new();
+
+        for neighbour in self.neighbours(point) {
+            if self[neighbour] == stone {
+                chain.extend(self.chain_at(neighbour));
+            } else if self[neighbour] == stone.not() && self.liberties(neighbour).len() == 1 {
+                captured.extend(self.chain_at(neighbour));
+            }
+        }
+
+        // Captured stones leave liberties behind wherever they touch the chain.
+        let liberties: HashSet<Point> = chain.iter()
+            .flat_map(|&p| self.neighbours(p))
+            .filter(|p| !chain.contains(p) && (self[*p] == Stone::Empty || captured.contains(p)))
+            .collect();
+
+        liberties.len() <= 1
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use board::{Board, Stone};
+    use point::Point;
+
+    fn chains(board: &Board, points: &[(usize, usize)]) -> HashSet<Point> {
+        points.iter().flat_map(|&p| board.chain_at(p)).collect()
+    }
+
+    #[test]
+    fn seki_without_eyes() {
+        // Black and white share the liberties at A7 and C7, and whoever fills one of them is
+        // captured.
+        let board = Board::from_str("\
+            .O.#O.. \
+            #O##O.. \
+            ###OO.. \
+            OOOO... \
+            ....... \
+            ....... \
+            .......");
+
+        assert_eq!(board.seki(), chains(&board, &[(1, 0), (3, 0)]));
+        assert!(board.in_seki((0, 1)));
+        assert!(!board.in_seki((4, 0)));
+        assert!(!board.in_seki((0, 0)));
+    }
+
+    #[test]
+    fn seki_with_an_eye_each() {
+        let board = Board::from_str("\
+            .#.O.O# \
+            ###OOO# \
+            OOO#### \
+            ....... \
+            ....... \
+            ....... \
+            .......");
+
+        assert_eq!(board.seki(), chains(&board, &[(1, 0), (3, 0)]));
+
+        // Once white fills their own eye, black can capture them.
+        let mut board = board;
+        board[(4, 0)] = Stone::White;
+
+        assert!(board.seki().is_empty());
+    }
+
+    #[test]
+    fn eye_against_no_eye() {
+        // Black can fill a shared liberty without being put in atari, so this is a capturing
+        // race that black wins rather than a seki.
+        let board = Board::from_str("\
+            .O.#O.. \
+            #O##O.. \
+            ###OO.. \
+            .#OO... \
+            ##O.... \
+            OOO.... \
+            .......");
+
+        assert!(board.seki().is_empty());
+    }
+}
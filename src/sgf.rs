@@ -0,0 +1,299 @@
+use std::collections::BTreeSet;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use point::Point;
+
+/// Options controlling how SGF files are written.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SgfOptions {
+    /// Whether to write lists of points (such as setup stones and markup) as compressed
+    /// rectangles like `AB[aa:cc]`, which is allowed from FF[4].
+    pub compress_points: bool,
+}
+
+/// A property of an SGF node, such as `AB[aa][bb]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Property {
+    pub ident: String,
+    pub values: Vec<String>,
+}
+
+/// A sequence of SGF nodes, followed by the variations that branch off from the last of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tree {
+    pub nodes: Vec<Vec<Property>>,
+    pub variations: Vec<Tree>,
+}
+
+impl Tree {
+    /// Returns the nodes of the main line of the tree (following the first variation whenever
+    /// there's a choice).
+    pub fn main_line(&self) -> Vec<&[Property]> {
+        let mut nodes: Vec<&[Property]> = Vec::new();
+        let mut tree = Some(self);
+
+        while let Some(t) = tree {
+            nodes.extend(t.nodes.iter().map(Vec::as_slice));
+            tree = t.variations.first();
+        }
+
+        nodes
+    }
+}
+
+/// Parses the game trees in an SGF collection. The parser is lenient: text outside of game
+/// trees is skipped, and unterminated trees and values are closed at the end of the input.
+pub fn parse(text: &str) -> Vec<Tree> {
+    let mut chars = text.chars().peekable();
+    let mut trees = Vec::new();
+
+    while let Some(c) = chars.next() {
+        if c == '(' {
+            trees.push(parse_tree(&mut chars));
+        }
+    }
+
+    trees
+}
+
+/// Parses a game tree, after its opening parenthesis.
+fn parse_tree(chars: &mut Peekable<Chars>) -> Tree {
+    let mut tree = Tree::default();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ';' => tree.nodes.push(parse_node(chars)),
+            '(' => tree.variations.push(parse_tree(chars)),
+            ')' => break,
+            _ => {},
+        }
+    }
+
+    tree
+}
+
+/// Parses the properties of a node, after its semicolon.
+fn parse_node(chars: &mut Peekable<Chars>) -> Vec<Property> {
+    let mut properties: Vec<Property> = Vec::new();
+    let mut ident = String::new();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            ';' | '(' | ')' => break,
+            '[' => {
+                chars.next();
+                let value = parse_value(chars);
+
+                // Additional values belong to the previous property, as in `AB[aa][bb]`.
+                if ident.is_empty() {
+                    if let Some(property) = properties.last_mut() {
+                        property.values.push(value);
+                    }
+                } else {
+                    properties.push(Property { ident: ident.clone(), values: vec![value] });
+                    ident.clear();
+                }
+            },
+            // Lowercase letters were allowed in identifiers in older versions of SGF (such as
+            // `AddBlack` for `AB`), and are ignored.
+            'A'..='Z' => { chars.next(); ident.push(c); },
+            _ => { chars.next(); },
+        }
+    }
+
+    properties
+}
+
+/// Parses a property value, after its opening bracket. Escaped characters are unescaped, and
+/// escaped line breaks (soft line breaks) are removed.
+fn parse_value(chars: &mut Peekable<Chars>) -> String {
+    let mut value = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ']' => break,
+            '\\' => match chars.next() {
+                Some(c @ '\n') | Some(c @ '\r') => {
+                    // Skip the other half of a two-character line break.
+                    let other = if c == '\n' { '\r' } else { '\n' };
+
+                    if chars.peek() == Some(&other) {
+                        chars.next();
+                    }
+                },
+                Some(c) => value.push(c),
+                None => {},
+            },
+            _ => value.push(c),
+        }
+    }
+
+    value
+}
+
+/// Parses a list of points, expanding compressed rectangles (such as `aa:cc`) into the points
+/// they cover. Values that aren't points on a board of the given `size` are skipped.
+pub fn parse_points(values: &[String], size: usize) -> Vec<Point> {
+    let mut points = Vec::new();
+
+    for value in values {
+        let mut corners = value.splitn(2, ':').map(|c| Point::from_sgf(c.trim(), size));
+
+        match (corners.next(), corners.next()) {
+            (Some(Some(point)), None) => points.push(point),
+            (Some(Some(a)), Some(Some(b))) => {
+                for y in a.y.min(b.y)..=a.y.max(b.y) {
+                    for x in a.x.min(b.x)..=a.x.max(b.x) {
+                        points.push(Point::new(x, y));
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    points
+}
+
+/// Formats a list of points as property values, covering them with as few rectangles as
+/// possible (greedily, from the top-left) if compression is enabled.
+pub fn format_points(points: &[Point], options: &SgfOptions) -> Vec<String> {
+    if !options.compress_points {
+        return points.iter().map(Point::to_sgf).collect();
+    }
+
+    // Points are ordered by row, then column.
+    let mut remaining: BTreeSet<(usize, usize)> = points.iter().map(|p| (p.y, p.x)).collect();
+    let mut values = Vec::new();
+
+    while let Some(&(y, x)) = remaining.iter().next() {
+        let mut width = 1;
+
+        while remaining.contains(&(y, x + width)) {
+            width += 1;
+        }
+
+        let mut height = 1;
+
+        while (x..x + width).all(|cx| remaining.contains(&(y + height, cx))) {
+            height += 1;
+        }
+
+        for cy in y..y + height {
+            for cx in x..x + width {
+                remaining.remove(&(cy, cx));
+            }
+        }
+
+        let (first, last) = (Point::new(x, y), Point::new(x + width - 1, y + height - 1));
+
+        if first == last {
+            values.push(first.to_sgf());
+        } else {
+            values.push(format!("{}:{}", first.to_sgf(), last.to_sgf()));
+        }
+    }
+
+    values
+}
+
+/// Escapes `text` for use as an SGF property value.
+pub fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+/// Writes a property with the given values, if there are any.
+pub fn write_property<S: AsRef<str>>(sgf: &mut String, ident: &str, values: &[S]) {
+    if values.is_empty() {
+        return;
+    }
+
+    sgf.push_str(ident);
+
+    for value in values {
+        sgf.push('[');
+        sgf.push_str(&escape(value.as_ref()));
+        sgf.push(']');
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, format_points, parse, parse_points, Property, SgfOptions, Tree};
+    use point::Point;
+
+    fn property(ident: &str, values: &[&str]) -> Property {
+        Property { ident: ident.to_string(), values: values.iter().map(|v| v.to_string()).collect() }
+    }
+
+    #[test]
+    fn nodes_and_properties() {
+        let trees = parse("(;GM[1]SZ[19]\n AB[aa] [bb];B[cc]C[Hello];W[dd])");
+
+        assert_eq!(trees, vec![Tree {
+            nodes: vec![
+                vec![property("GM", &["1"]), property("SZ", &["19"]), property("AB", &["aa", "bb"])],
+                vec![property("B", &["cc"]), property("C", &["Hello"])],
+                vec![property("W", &["dd"])],
+            ],
+            variations: vec![],
+        }]);
+    }
+
+    #[test]
+    fn variations() {
+        let trees = parse("(;SZ[9];B[aa](;W[bb];B[cc])(;W[dd]))(;SZ[13])");
+
+        assert_eq!(trees.len(), 2);
+        assert_eq!(trees[0].variations.len(), 2);
+
+        let main_line = trees[0].main_line();
+        assert_eq!(main_line.len(), 4);
+        assert_eq!(main_line[3], &[property("B", &["cc"])][..]);
+    }
+
+    #[test]
+    fn escaping() {
+        let trees = parse("(;C[a \\] b \\\\ c\\\nd\ne]N[x])");
+
+        assert_eq!(trees[0].nodes[0][0], property("C", &["a ] b \\ cd\ne"]));
+        assert_eq!(escape("a ] b \\ c"), "a \\] b \\\\ c");
+    }
+
+    #[test]
+    fn lenient() {
+        assert_eq!(parse("garbage"), vec![]);
+        assert_eq!(parse("(;AddBlack[aa]ViewAll[]").len(), 1);
+        assert_eq!(parse("(;AddBlack[aa]")[0].nodes[0], vec![property("AB", &["aa"])]);
+        assert_eq!(parse("(;C[unterminated")[0].nodes[0], vec![property("C", &["unterminated"])]);
+    }
+
+    #[test]
+    fn compressed_points() {
+        let values: Vec<String> = vec!["aa:bc".into(), "dd".into(), "ee:dd".into(), "tt".into(), "aa:tt".into()];
+
+        assert_eq!(parse_points(&values, 19), vec![
+            Point::new(0, 0), Point::new(1, 0),
+            Point::new(0, 1), Point::new(1, 1),
+            Point::new(0, 2), Point::new(1, 2),
+            Point::new(3, 3),
+            Point::new(3, 3), Point::new(4, 3),
+            Point::new(3, 4), Point::new(4, 4),
+        ]);
+    }
+
+    #[test]
+    fn compress_points() {
+        let points = parse_points(&["aa:cb".to_string(), "ac".to_string(), "ee".to_string()], 19);
+        let compressed = SgfOptions { compress_points: true };
+
+        assert_eq!(format_points(&points, &SgfOptions::default()), vec!["aa", "ba", "ca", "ab", "bb", "cb", "ac", "ee"]);
+        assert_eq!(format_points(&points, &compressed), vec!["aa:cb", "ac", "ee"]);
+
+        // An L shape is covered by a row and then a column.
+        let points = parse_points(&["aa:ca".to_string(), "ab:ad".to_string()], 19);
+        assert_eq!(format_points(&points, &compressed), vec!["aa:ca", "ab:ad"]);
+    }
+}
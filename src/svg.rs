@@ -0,0 +1,380 @@
+use std::fmt::Write;
+
+use board::{Board, Stone};
+use markup::Markup;
+use point::{self, Point};
+
+const BOARD_COLOR: &str = "#dcb35c";
+
+/// Options controlling how `Board::to_svg` draws a diagram.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// Distance between adjacent grid lines, in pixels.
+    pub spacing: f64,
+    /// Whether to draw GTP-style coordinate labels along the top and left edges. Columns are
+    /// labelled with SGF letters instead on boards too large for GTP.
+    pub coordinates: bool,
+    /// Numbers to draw on top of stones (or empty points), such as move numbers.
+    pub numbers: Vec<(Point, usize)>,
+    /// Markup to draw on top of points.
+    pub markup: Vec<(Point, Markup)>,
+    /// The top-left and bottom-right corners (inclusive) of the part of the board to draw, or
+    /// `None` to draw the whole board.
+    pub region: Option<(Point, Point)>,
+    /// Ownership of each point (see `Board::ownership`) to draw as a heat map of squares, whose
+    /// opacity shows how certainly the point is owned.
+    pub ownership: Option<Vec<f32>>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> SvgOptions {
+        SvgOptions {
+            spacing: 24.0,
+            coordinates: false,
+            numbers: Vec::new(),
+            markup: Vec::new(),
+            region: None,
+            ownership: None,
+        }
+    }
+}
+
+/// Maps points in the drawn region of the board to pixel coordinates.
+struct Layout {
+    spacing: f64,
+    offset: f64,
+    min: Point,
+    max: Point,
+}
+
+impl Layout {
+    fn x(&self, x: usize) -> f64 {
+        round(self.offset + (x - self.min.x) as f64 * self.spacing)
+    }
+
+    fn y(&self, y: usize) -> f64 {
+        round(self.offset + (y - self.min.y) as f64 * self.spacing)
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+}
+
+impl Board {
+    /// Renders the board as an SVG diagram.
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let mut svg = String::new();
+
+        // A board without any points is drawn as just its margin.
+        if self.size == 0 {
+            let side = 2.0 * options.spacing;
+
+            writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+                side, side, side, side).unwrap();
+            writeln!(svg, r#"<rect width="{}" height="{}" fill="{}"/>"#, side, side, BOARD_COLOR).unwrap();
+            svg.push_str("</svg>\n");
+            return svg;
+        }
+
+        let last = self.size - 1;
+        let (min, max) = match options.region {
+            Some((a, b)) => (
+                Point::new(a.x.min(b.x).min(last), a.y.min(b.y).min(last)),
+                Point::new(a.x.max(b.x).min(last), a.y.max(b.y).min(last)),
+            ),
+            None => (Point::new(0, 0), Point::new(last, last)),
+        };
+
+        let s = options.spacing;
+        let labels = if options.coordinates { s } else { 0.0 };
+        let layout = Layout { spacing: s, offset: labels + s, min, max };
+
+        let width = labels + (max.x - min.x + 2) as f64 * s;
+        let height = labels + (max.y - min.y + 2) as f64 * s;
+
+        writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            width, height, width, height).unwrap();
+        writeln!(svg, r#"<rect width="{}" height="{}" fill="{}"/>"#, width, height, BOARD_COLOR).unwrap();
+
+        // Grid lines extend halfway to the next line where the region is cropped, to show that
+        // the board continues in that direction.
+        let extend = |cropped: bool| if cropped { s / 2.0 } else { 0.0 };
+
+        for y in min.y..=max.y {
+            writeln!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black"/>"#,
+                layout.x(min.x) - extend(min.x > 0), layout.y(y),
+                layout.x(max.x) + extend(max.x < last), layout.y(y)).unwrap();
+        }
+
+        for x in min.x..=max.x {
+            writeln!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black"/>"#,
+                layout.x(x), layout.y(min.y) - extend(min.y > 0),
+                layout.x(x), layout.y(max.y) + extend(max.y < last)).unwrap();
+        }
+
+        if options.coordinates {
+            for x in min.x..=max.x {
+                if let Some(column) = point::column_label(x, self.size) {
+                    write_text(&mut svg, layout.x(x), labels / 2.0 + s / 4.0, s, "black",
+                        &column.to_string());
+                }
+            }
+
+            for y in min.y..=max.y {
+                write_text(&mut svg, labels / 2.0 + s / 4.0, layout.y(y), s, "black",
+                    &(self.size - y).to_string());
+            }
+        }
+
+        let points: Vec<Point> = self.points().filter(|&p| layout.contains(p)).collect();
+
+        for &point in points.iter().filter(|&&p| self.star_point(p)) {
+            writeln!(svg, r#"<circle cx="{}" cy="{}" r="{}" fill="black"/>"#,
+                layout.x(point.x), layout.y(point.y), round(s * 0.1)).unwrap();
+        }
+
+        for &point in &points {
+            let fill = match self[point] {
+                Stone::Empty => continue,
+                Stone::Black => "black",
+                Stone::White => "white",
+            };
+
+            writeln!(svg, r#"<circle cx="{}" cy="{}" r="{}" fill="{}" stroke="black"/>"#,
+                layout.x(point.x), layout.y(point.y), round(s * 0.475), fill).unwrap();
+        }
+
+        if let Some(ref ownership) = options.ownership {
+            let r = round(s * 0.2);
+
+            for &point in &points {
+                let owner = ownership.get(point.y * self.size + point.x).cloned().unwrap_or(0.0);
+                let fill = if owner > 0.0 { Stone::Black } else { Stone::White };
+
+                // Stones that are owned by their own color don't need a square.
+                if owner.abs() < 0.1 || self[point] == fill {
+                    continue;
+                }
+
+                writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{}"/>"#,
+                    round(layout.x(point.x) - r), round(layout.y(point.y) - r), 2.0 * r, 2.0 * r,
+                    if fill == Stone::Black { "black" } else { "white" }, round(owner.abs() as f64)).unwrap();
+            }
+        }
+
+        for &(point, number) in options.numbers.iter().filter(|&&(p, _)| layout.contains(p)) {
+            self.clear_empty(&mut svg, &layout, point);
+            write_text(&mut svg, layout.x(point.x), layout.y(point.y), s, self.contrast(point),
+                &number.to_string());
+        }
+
+        for &(point, ref markup) in options.markup.iter().filter(|&&(p, _)| layout.contains(p)) {
+            let (cx, cy) = (layout.x(point.x), layout.y(point.y));
+            let color = self.contrast(point);
+            let r = round(s * 0.25);
+            let (dx, dy) = (round(r * 0.866), round(r * 0.5));
+
+            match *markup {
+                Markup::Circle => {
+                    writeln!(svg, r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                        cx, cy, r, color).unwrap();
+                },
+                Markup::Cross => {
+                    writeln!(svg, r#"<path d="M{} {}L{} {}M{} {}L{} {}" stroke="{}" stroke-width="2"/>"#,
+                        round(cx - r), round(cy - r), round(cx + r), round(cy + r),
+                        round(cx - r), round(cy + r), round(cx + r), round(cy - r), color).unwrap();
+                },
+                Markup::Square => {
+                    writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+                        round(cx - r), round(cy - r), 2.0 * r, 2.0 * r, color).unwrap();
+                },
+                Markup::Triangle => {
+                    writeln!(svg, r#"<path d="M{} {}L{} {}L{} {}Z" fill="none" stroke="{}" stroke-width="2"/>"#,
+                        cx, round(cy - r), round(cx + dx), round(cy + dy), round(cx - dx), round(cy + dy),
+                        color).unwrap();
+                },
+                Markup::Label(ref label) => {
+                    self.clear_empty(&mut svg, &layout, point);
+                    write_text(&mut svg, cx, cy, s, color, &escape(label));
+                },
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Returns the color that text and markup drawn on top of `point` should be.
+    fn contrast(&self, point: Point) -> &'static str {
+        if self[point] == Stone::Black { "white" } else { "black" }
+    }
+
+    /// Hides the grid lines at `point` if it is empty, so that text drawn on it is legible.
+    fn clear_empty(&self, svg: &mut String, layout: &Layout, point: Point) {
+        if self[point] == Stone::Empty {
+            writeln!(svg, r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                layout.x(point.x), layout.y(point.y), round(layout.spacing * 0.4), BOARD_COLOR).unwrap();
+        }
+    }
+}
+
+/// Writes `text` centered at `(x, y)`, sized for a grid with the given `spacing`.
+fn write_text(svg: &mut String, x: f64, y: f64, spacing: f64, color: &str, text: &str) {
+    let font_size = round(if text.chars().count() > 2 { spacing * 0.4 } else { spacing * 0.5 });
+
+    writeln!(svg, r#"<text x="{}" y="{}" font-family="sans-serif" font-size="{}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+        round(x), round(y), font_size, color, text).unwrap();
+}
+
+/// Rounds `value` to two decimal places, to keep the generated SVG compact.
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Escapes characters that have a special meaning in XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::SvgOptions;
+    use board::{Board, Stone};
+    use markup::Markup;
+    use point::Point;
+
+    #[test]
+    fn empty_board() {
+        let svg = Board::with_size(9).to_svg(&SvgOptions::default());
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(r#"viewBox="0 0 240 240""#));
+        assert_eq!(svg.matches("<line").count(), 18);
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert!(!svg.contains("<text"));
+    }
+
+    #[test]
+    fn stones() {
+        let board = Board::from_str("\
+            .#. \
+            #O# \
+            .#.");
+
+        let svg = board.to_svg(&SvgOptions::default());
+
+        assert_eq!(svg.matches(r#"fill="black" stroke="black""#).count(), 4);
+        assert_eq!(svg.matches(r#"fill="white" stroke="black""#).count(), 1);
+        assert!(svg.contains(r#"<circle cx="48" cy="48" r="11.4" fill="white""#));
+    }
+
+    #[test]
+    fn coordinates() {
+        let options = SvgOptions { coordinates: true, ..Default::default() };
+        let svg = Board::with_size(19).to_svg(&options);
+
+        assert!(svg.contains(r#"viewBox="0 0 504 504""#));
+        assert!(svg.contains(">A</text>"));
+        assert!(svg.contains(">T</text>"));
+        assert!(!svg.contains(">I</text>"));
+        assert!(svg.contains(">19</text>"));
+        assert!(svg.contains(">1</text>"));
+    }
+
+    #[test]
+    fn coordinates_on_large_boards() {
+        let options = SvgOptions { coordinates: true, ..Default::default() };
+        let svg = Board::with_size(30).to_svg(&options);
+
+        assert!(svg.contains(">a</text>"));
+        assert!(svg.contains(">i</text>"));
+        assert!(svg.contains(">D</text>"));
+        assert!(svg.contains(">30</text>"));
+    }
+
+    #[test]
+    fn empty_size() {
+        let svg = Board::with_size(0).to_svg(&SvgOptions { coordinates: true, ..Default::default() });
+
+        assert!(svg.contains(r#"viewBox="0 0 48 48""#));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(!svg.contains("<line"));
+    }
+
+    #[test]
+    fn markup_and_numbers() {
+        let board = Board::from_str("\
+            #O. \
+            ... \
+            ...");
+
+        let options = SvgOptions {
+            numbers: vec![(Point::new(0, 0), 1), (Point::new(1, 0), 2)],
+            markup: vec![
+                (Point::new(0, 1), Markup::Triangle),
+                (Point::new(1, 1), Markup::Square),
+                (Point::new(2, 1), Markup::Circle),
+                (Point::new(0, 2), Markup::Cross),
+                (Point::new(1, 2), Markup::Label("<A>".to_string())),
+            ],
+            ..Default::default()
+        };
+
+        let svg = board.to_svg(&options);
+
+        assert!(svg.contains(r#"fill="white" text-anchor="middle" dominant-baseline="central">1</text>"#));
+        assert!(svg.contains(r#"fill="black" text-anchor="middle" dominant-baseline="central">2</text>"#));
+        assert!(svg.contains("&lt;A&gt;</text>"));
+        assert_eq!(svg.matches("<path").count(), 2);
+        assert_eq!(svg.matches(r#"<rect x="#).count(), 1);
+        assert_eq!(svg.matches(r#"fill="none" stroke="black" stroke-width="2""#).count(), 3);
+    }
+
+    #[test]
+    fn ownership() {
+        let board = Board::from_str("\
+            .#O \
+            .#O \
+            O#O");
+
+        let options = SvgOptions {
+            ownership: Some(vec![1.0, 1.0, -1.0, 0.5, 1.0, -1.0, 0.75, 1.0, -1.0]),
+            ..Default::default()
+        };
+
+        let svg = board.to_svg(&options);
+
+        assert_eq!(svg.matches("<rect x=").count(), 3);
+        assert!(svg.contains(r#"<rect x="19.2" y="19.2" width="9.6" height="9.6" fill="black" fill-opacity="1"/>"#));
+        assert!(svg.contains(r#"fill="black" fill-opacity="0.5"/>"#));
+
+        // The dead white stone is marked as black's.
+        assert!(svg.contains(r#"<rect x="19.2" y="67.2" width="9.6" height="9.6" fill="black" fill-opacity="0.75"/>"#));
+    }
+
+    #[test]
+    fn cropped_region() {
+        let mut board = Board::with_size(19);
+        board[(15, 3)] = Stone::Black;
+        board[(2, 16)] = Stone::White;
+
+        let options = SvgOptions {
+            region: Some((Point::new(18, 0), Point::new(10, 8))),
+            ..Default::default()
+        };
+
+        let svg = board.to_svg(&options);
+
+        // 9x9 points, plus a margin of one line spacing on each side.
+        assert!(svg.contains(r#"viewBox="0 0 240 240""#));
+        assert_eq!(svg.matches(r#"fill="black" stroke="black""#).count(), 1);
+        assert_eq!(svg.matches(r#"fill="white" stroke="black""#).count(), 0);
+
+        // Lines are extended to the left and bottom, where the board continues.
+        assert!(svg.contains(r#"<line x1="12" y1="24" x2="216" y2="24" stroke="black"/>"#));
+        assert!(svg.contains(r#"<line x1="24" y1="24" x2="24" y2="228" stroke="black"/>"#));
+    }
+}
@@ -0,0 +1,339 @@
+use board::{Board, Stone};
+use point::{self, Point};
+
+const ANSI_BOARD: &str = "\x1b[48;5;179m";
+const ANSI_LAST_MOVE: &str = "\x1b[48;5;167m";
+const ANSI_BLACK: &str = "\x1b[38;5;16m";
+const ANSI_WHITE: &str = "\x1b[38;5;231m";
+const ANSI_EMPTY: &str = "\x1b[38;5;94m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Backgrounds for points owned by black and white, from least to most certain.
+const ANSI_BLACK_AREA: [&str; 3] = ["\x1b[48;5;137m", "\x1b[48;5;95m", "\x1b[48;5;239m"];
+const ANSI_WHITE_AREA: [&str; 3] = ["\x1b[48;5;180m", "\x1b[48;5;223m", "\x1b[48;5;230m"];
+
+/// Renders a `Board` as text, with options for terminals and plain-text output. The default
+/// formatter produces the same output as `impl Display for Board`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BoardFormatter {
+    /// Whether to label the columns (skipping `I`) and rows the way GTP does. Columns are
+    /// labelled with SGF letters instead on boards too large for GTP.
+    pub coordinates: bool,
+    /// Whether to use `X`, `O`, `.` and `+` instead of Unicode glyphs.
+    pub ascii: bool,
+    /// Point to highlight as the most recently played move.
+    pub last_move: Option<Point>,
+    /// Whether to color the board and stones with ANSI escape codes.
+    pub color: bool,
+    /// Whether to omit the spaces between points.
+    pub compact: bool,
+    /// Ownership of each point (see `Board::ownership`) to show as a heat map. With colors,
+    /// points are shaded by how certainly they are owned; without, empty points that are more
+    /// likely than not to be owned are marked with the owner's glyph.
+    pub ownership: Option<Vec<f32>>,
+}
+
+impl BoardFormatter {
+    /// Creates a formatter with every option disabled.
+    pub fn new() -> BoardFormatter {
+        BoardFormatter { ..Default::default() }
+    }
+
+    /// Renders `board` as text, one line per row.
+    pub fn format(&self, board: &Board) -> String {
+        let mut rows = Vec::new();
+        let label_width = board.size.to_string().len();
+
+        if self.coordinates {
+            rows.push(self.column_labels(board, label_width));
+        }
+
+        for y in 0..board.size {
+            let mut row = String::new();
+
+            if self.coordinates {
+                row.push_str(&format!("{:>1$}", board.size - y, label_width));
+            }
+
+            if self.color {
+                row.push_str(ANSI_BOARD);
+            }
+
+            for x in 0..board.size {
+                let point = Point::new(x, y);
+                let last = self.last_move == Some(point);
+
+                // Without colors, the last move is highlighted by surrounding it with parentheses
+                // in place of the usual spaces between points.
+                if !self.compact {
+                    let previous = x.checked_sub(1).map(|px| Point::new(px, y));
+
+                    if last && !self.color {
+                        row.push('(');
+                    } else if previous.is_some() && previous == self.last_move && !self.color {
+                        row.push(')');
+                    } else if x > 0 || self.coordinates {
+                        row.push(' ');
+                    }
+                }
+
+                let background = match self.color {
+                    true if last => Some(ANSI_LAST_MOVE),
+                    true => self.area_background(board, point),
+                    false => None,
+                };
+
+                if let Some(background) = background {
+                    row.push_str(background);
+                }
+
+                row.push_str(&self.glyph(board, point, last));
+
+                if background.is_some() {
+                    row.push_str(ANSI_BOARD);
+                }
+            }
+
+            if !self.compact && !self.color && self.last_move == Some(Point::new(board.size - 1, y)) {
+                row.push(')');
+            }
+
+            if self.color {
+                row.push_str(ANSI_RESET);
+            }
+
+            if self.coordinates {
+                let sep = if self.compact || row.ends_with(')') { "" } else { " " };
+                row.push_str(&format!("{}{}", sep, board.size - y));
+            }
+
+            rows.push(row);
+        }
+
+        if self.coordinates {
+            rows.push(self.column_labels(board, label_width));
+        }
+
+        rows.join("\n")
+    }
+
+    /// Returns the line of column labels shown above and below the board.
+    fn column_labels(&self, board: &Board, label_width: usize) -> String {
+        let sep = if self.compact { "" } else { " " };
+        let labels: Vec<String> = (0..board.size)
+            .map(|x| point::column_label(x, board.size).unwrap_or(' ').to_string())
+            .collect();
+
+        format!("{}{}{}", " ".repeat(label_width), sep, labels.join(sep))
+    }
+
+    /// Returns the ownership of `point`, if a heat map is being shown.
+    fn owner(&self, board: &Board, point: Point) -> Option<f32> {
+        self.ownership.as_ref().and_then(|o| o.get(point.y * board.size + point.x)).cloned()
+    }
+
+    /// Returns the background to shade `point` with, based on how certainly it is owned.
+    fn area_background(&self, board: &Board, point: Point) -> Option<&'static str> {
+        let owner = self.owner(board, point)?;
+        let level = ((owner.abs() * 4.0) as usize).min(4).checked_sub(1)?;
+
+        Some(if owner > 0.0 { ANSI_BLACK_AREA[level.min(2)] } else { ANSI_WHITE_AREA[level.min(2)] })
+    }
+
+    /// Returns the text representing the contents of `point`.
+    fn glyph(&self, board: &Board, point: Point, last: bool) -> String {
+        let stone = board[point];
+
+        // In compact mode there's no room for parentheses, so the last move gets its own glyph.
+        let marked = last && self.compact && !self.color;
+
+        // Without colors, empty points in someone's area are marked with a small square.
+        let area = match self.owner(board, point) {
+            Some(owner) if !self.color && stone == Stone::Empty && owner.abs() >= 0.5 => {
+                Some(if owner > 0.0 { Stone::Black } else { Stone::White })
+            },
+            _ => None,
+        };
+
+        let glyph = match (stone, self.ascii) {
+            _ if area.is_some() => match (area == Some(Stone::Black), self.ascii) {
+                (true, true) => "x",
+                (false, true) => "o",
+                (true, false) => "▪", // U+25AA BLACK SMALL SQUARE
+                (false, false) => "▫", // U+25AB WHITE SMALL SQUARE
+            },
+            (Stone::Empty, true) if board.star_point(point) => "+",
+            (Stone::Empty, true) => ".",
+            (Stone::Black, true) => if marked { "x" } else { "X" },
+            (Stone::White, true) => if marked { "o" } else { "O" },
+            (Stone::Empty, false) if board.star_point(point) => "•", // U+2022 BULLET
+            (Stone::Empty, false) => "⋅", // U+22C5 DOT OPERATOR
+            (Stone::Black, false) => if marked { "◉" } else { "●" }, // U+25C9 FISHEYE
+            (Stone::White, false) => if marked { "◎" } else { "○" }, // U+25CE BULLSEYE
+        };
+
+        if self.color {
+            let color = match stone {
+                Stone::Empty => ANSI_EMPTY,
+                Stone::Black => ANSI_BLACK,
+                Stone::White => ANSI_WHITE,
+            };
+
+            format!("{}{}", color, glyph)
+        } else {
+            glyph.to_string()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::BoardFormatter;
+    use board::{Board, Stone};
+    use point::Point;
+
+    fn board() -> Board {
+        Board::from_str("\
+            #O... \
+            ..... \
+            ..... \
+            ..... \
+            ....#")
+    }
+
+    #[test]
+    fn default_matches_display() {
+        let board = board();
+        assert_eq!(BoardFormatter::new().format(&board), format!("{}", board));
+        assert_eq!(BoardFormatter::new().format(&board), "\
+            ● ○ ⋅ ⋅ ⋅\n\
+            ⋅ ⋅ ⋅ ⋅ ⋅\n\
+            ⋅ ⋅ ⋅ ⋅ ⋅\n\
+            ⋅ ⋅ ⋅ ⋅ ⋅\n\
+            ⋅ ⋅ ⋅ ⋅ ●");
+    }
+
+    #[test]
+    fn ascii() {
+        let formatter = BoardFormatter { ascii: true, ..Default::default() };
+        let board = Board::with_size(9);
+
+        assert_eq!(formatter.format(&board), "\
+            . . . . . . . . .\n\
+            . . . . . . . . .\n\
+            . . + . . . + . .\n\
+            . . . . . . . . .\n\
+            . . . . + . . . .\n\
+            . . . . . . . . .\n\
+            . . + . . . + . .\n\
+            . . . . . . . . .\n\
+            . . . . . . . . .");
+    }
+
+    #[test]
+    fn coordinates() {
+        let formatter = BoardFormatter { coordinates: true, ascii: true, ..Default::default() };
+
+        assert_eq!(formatter.format(&board()), "  A B C D E\n\
+            5 X O . . . 5\n\
+            4 . . . . . 4\n\
+            3 . . . . . 3\n\
+            2 . . . . . 2\n\
+            1 . . . . X 1\n  \
+              A B C D E");
+
+        let board = Board::with_size(19);
+        let lines: Vec<String> = formatter.format(&board).lines().map(String::from).collect();
+        assert_eq!(lines[0], "   A B C D E F G H J K L M N O P Q R S T");
+        assert!(lines[1].starts_with("19 . . ."));
+        assert!(lines[19].starts_with(" 1 . . ."));
+    }
+
+    #[test]
+    fn coordinates_on_large_boards() {
+        let formatter = BoardFormatter { coordinates: true, ascii: true, ..Default::default() };
+        let mut board = Board::with_size(30);
+        board[(0, 0)] = Stone::Black;
+
+        let lines: Vec<String> = formatter.format(&board).lines().map(String::from).collect();
+        assert_eq!(lines[0], "   a b c d e f g h i j k l m n o p q r s t u v w x y z A B C D");
+        assert!(lines[1].starts_with("30 X . ."));
+        assert_eq!(lines[31], lines[0]);
+    }
+
+    #[test]
+    fn last_move() {
+        let mut formatter = BoardFormatter { ascii: true, ..Default::default() };
+
+        formatter.last_move = Some(Point::new(1, 0));
+        assert!(formatter.format(&board()).starts_with("X(O). . .\n"));
+
+        formatter.last_move = Some(Point::new(4, 4));
+        assert!(formatter.format(&board()).ends_with("\n. . . .(X)"));
+
+        formatter.coordinates = true;
+        formatter.last_move = Some(Point::new(0, 0));
+        assert!(formatter.format(&board()).contains("\n5(X)O . . . 5\n"));
+
+        formatter.coordinates = false;
+        formatter.compact = true;
+        assert!(formatter.format(&board()).starts_with("xO...\n"));
+    }
+
+    #[test]
+    fn compact() {
+        let formatter = BoardFormatter { compact: true, coordinates: true, ..Default::default() };
+
+        assert_eq!(formatter.format(&board()), " ABCDE\n\
+            5●○⋅⋅⋅5\n\
+            4⋅⋅⋅⋅⋅4\n\
+            3⋅⋅⋅⋅⋅3\n\
+            2⋅⋅⋅⋅⋅2\n\
+            1⋅⋅⋅⋅●1\n \
+             ABCDE");
+    }
+
+    #[test]
+    fn ownership_heat_map() {
+        let board = Board::from_str("\
+            .#O \
+            .#O \
+            .#O");
+
+        let ownership = vec![0.9, 1.0, -1.0, 0.3, 1.0, -1.0, 0.5, 1.0, -1.0];
+        let mut formatter = BoardFormatter { ascii: true, ownership: Some(ownership), ..Default::default() };
+
+        assert_eq!(formatter.format(&board), "x X O\n. X O\nx X O");
+
+        formatter.ascii = false;
+        assert!(formatter.format(&board).starts_with("▪ ● ○"));
+
+        formatter.color = true;
+        let text = formatter.format(&board);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert!(lines[0].starts_with("\x1b[48;5;179m\x1b[48;5;239m\x1b[38;5;94m⋅\x1b[48;5;179m"));
+        assert!(lines[0].contains("\x1b[48;5;230m\x1b[38;5;231m○"));
+        assert!(lines[1].starts_with("\x1b[48;5;179m\x1b[48;5;137m\x1b[38;5;94m⋅"));
+        assert!(lines[2].starts_with("\x1b[48;5;179m\x1b[48;5;95m"));
+    }
+
+    #[test]
+    fn ansi_colors() {
+        let formatter = BoardFormatter {
+            color: true,
+            ascii: true,
+            last_move: Some(Point::new(1, 0)),
+            ..Default::default()
+        };
+
+        let text = formatter.format(&board());
+        let first = text.lines().next().unwrap();
+
+        assert!(first.starts_with("\x1b[48;5;179m\x1b[38;5;16mX \x1b[48;5;167m\x1b[38;5;231mO\x1b[48;5;179m"));
+        assert!(first.ends_with("\x1b[0m"));
+        assert!(!text.contains('('));
+        assert_eq!(text.lines().count(), 5);
+    }
+}
@@ -1,6 +1,6 @@
 extern crate joseki;
 
-use joseki::Game;
+use joseki::{Game, Point};
 
 #[test]
 fn read_game() {
@@ -11,3 +11,56 @@ fn read_game() {
     assert!(game_str.contains("● ● ● ○ ○ ● ○ ⋅ ○ ○ ● ● ● ○ ● ⋅ ● ○ ⋅"));
     assert!(game_str.contains("⋅ ○ ⋅ ○ ● ○ ⋅ ○ ⋅ ○ ⋅ ⋅ ● ⋅ ● • ○ ⋅ ⋅"));
 }
+
+#[test]
+fn sgf_round_trip() {
+    let game = Game::open("tests/games/AlphaGo-Lee_Sedol-1.sgf").unwrap();
+    let copy = Game::parse(&game.to_sgf()).unwrap();
+
+    assert_eq!(copy.board(), game.board());
+    assert_eq!(copy.moves(), game.moves());
+    assert_eq!(format!("{}", copy), format!("{}", game));
+}
+
+#[test]
+fn dead_stones_in_real_games() {
+    let estimate = |filename: &str, alive: &[&str], dead: &[&str]| {
+        let game = Game::open(filename).unwrap();
+        let dead_stones = game.dead_stones();
+
+        for vertex in alive {
+            let point = Point::from_gtp(vertex, game.board().size).unwrap();
+            assert!(!dead_stones.contains(&point), "{} in {} is marked dead", vertex, filename);
+        }
+
+        for vertex in dead {
+            let point = Point::from_gtp(vertex, game.board().size).unwrap();
+            assert!(dead_stones.contains(&point), "{} in {} isn't marked dead", vertex, filename);
+        }
+
+        game.estimate_score()
+    };
+
+    // White's group in the lower right has a six point eye space in the corner.
+    let score = estimate("tests/games/AlphaGo-Lee_Sedol-1.sgf", &["Q4", "R3", "R4", "R5", "S1", "S2", "S5", "T5"], &[]);
+    assert!(score < 0.0);
+
+    let score = estimate("tests/games/AlphaGo-Lee_Sedol-2.sgf", &[], &[]);
+    assert!(score > 0.0);
+
+    // Black's invasion at the bottom only has one eye, since K1 is a false eye, and white's
+    // stones surround it without fully enclosing an area.
+    let score = estimate(
+        "tests/games/AlphaGo-Lee_Sedol-3.sgf",
+        &["C3", "D3", "E3", "F3", "G3", "D4", "E5", "F5"],
+        &["G1", "J3", "K2", "L1"],
+    );
+    assert!(score < 0.0);
+
+    let score = estimate("tests/games/AlphaGo-Lee_Sedol-4.sgf", &[], &[]);
+    assert!(score < 0.0);
+
+    // White's stones on the right edge can capture the black stone at S6, which is in atari.
+    let score = estimate("tests/games/AlphaGo-Lee_Sedol-5.sgf", &["S4", "S5", "S7", "T5", "T6", "T7"], &[]);
+    assert!(score < 0.0);
+}